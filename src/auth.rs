@@ -3,20 +3,488 @@
 use crate::reddit::{Error, Result};
 
 use reqwest::{
-    blocking::{Client, Response},
+    blocking::{Client, RequestBuilder, Response},
     header::{HeaderValue, AUTHORIZATION},
-    StatusCode,
+    StatusCode, Url,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::RwLock;
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    sync::RwLock,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// An access token.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     pub access_token: String,
     pub expires_in: i32,
     pub scope: String,
     pub token_type: String,
+    /// The refresh token, if Reddit returned one alongside this access token.
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// When this token was issued, in seconds since the UNIX epoch.
+    #[serde(default)]
+    pub issued_at: u64,
+}
+
+impl Token {
+    /// Whether this access token has expired, judging by [`Self::issued_at`] and [`Self::expires_in`].
+    pub fn is_expired(&self) -> bool {
+        now() >= self.issued_at + self.expires_in.max(0) as u64
+    }
+
+    /// The scopes granted to this token, parsed from [`Self::scope`]. Scopes snew does not know about are left out.
+    pub fn scopes(&self) -> Vec<Scope> {
+        self.scope
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+
+    /// Whether this token was granted `scope`, either directly or through [`Scope::All`].
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes()
+            .into_iter()
+            .any(|granted| granted == scope || granted == Scope::All)
+    }
+}
+
+/// An OAuth scope, limiting what a token can be used for. See <https://www.reddit.com/api/v1/scopes> for what each one allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Every scope, `*`.
+    All,
+    Account,
+    Creddits,
+    Edit,
+    Flair,
+    History,
+    Identity,
+    LiveManage,
+    ModConfig,
+    ModContributors,
+    ModFlair,
+    ModLog,
+    ModMail,
+    ModOthers,
+    ModPosts,
+    ModSelf,
+    ModWiki,
+    MySubreddits,
+    PrivateMessages,
+    Read,
+    Report,
+    Save,
+    StructuredStyles,
+    Submit,
+    Subscribe,
+    Vote,
+    WikiEdit,
+    WikiRead,
+}
+
+impl Scope {
+    /// The name Reddit uses for this scope.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "*",
+            Self::Account => "account",
+            Self::Creddits => "creddits",
+            Self::Edit => "edit",
+            Self::Flair => "flair",
+            Self::History => "history",
+            Self::Identity => "identity",
+            Self::LiveManage => "livemanage",
+            Self::ModConfig => "modconfig",
+            Self::ModContributors => "modcontributors",
+            Self::ModFlair => "modflair",
+            Self::ModLog => "modlog",
+            Self::ModMail => "modmail",
+            Self::ModOthers => "modothers",
+            Self::ModPosts => "modposts",
+            Self::ModSelf => "modself",
+            Self::ModWiki => "modwiki",
+            Self::MySubreddits => "mysubreddits",
+            Self::PrivateMessages => "privatemessages",
+            Self::Read => "read",
+            Self::Report => "report",
+            Self::Save => "save",
+            Self::StructuredStyles => "structuredstyles",
+            Self::Submit => "submit",
+            Self::Subscribe => "subscribe",
+            Self::Vote => "vote",
+            Self::WikiEdit => "wikiedit",
+            Self::WikiRead => "wikiread",
+        }
+    }
+
+    /// Join scopes the way Reddit expects them in the `scope` parameter of the authorization URL.
+    pub fn join(scopes: &[Scope]) -> String {
+        scopes
+            .iter()
+            .map(Scope::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Scope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "*" => Self::All,
+            "account" => Self::Account,
+            "creddits" => Self::Creddits,
+            "edit" => Self::Edit,
+            "flair" => Self::Flair,
+            "history" => Self::History,
+            "identity" => Self::Identity,
+            "livemanage" => Self::LiveManage,
+            "modconfig" => Self::ModConfig,
+            "modcontributors" => Self::ModContributors,
+            "modflair" => Self::ModFlair,
+            "modlog" => Self::ModLog,
+            "modmail" => Self::ModMail,
+            "modothers" => Self::ModOthers,
+            "modposts" => Self::ModPosts,
+            "modself" => Self::ModSelf,
+            "modwiki" => Self::ModWiki,
+            "mysubreddits" => Self::MySubreddits,
+            "privatemessages" => Self::PrivateMessages,
+            "read" => Self::Read,
+            "report" => Self::Report,
+            "save" => Self::Save,
+            "structuredstyles" => Self::StructuredStyles,
+            "submit" => Self::Submit,
+            "subscribe" => Self::Subscribe,
+            "vote" => Self::Vote,
+            "wikiedit" => Self::WikiEdit,
+            "wikiread" => Self::WikiRead,
+            _ => return Err(Error::UnknownScope(s.to_string())),
+        })
+    }
+}
+
+/// How long a token received through the code flow lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenDuration {
+    /// The token expires after an hour, and no refresh token is returned.
+    Temporary,
+    /// A refresh token is returned, which can be used to get new tokens indefinitely.
+    Permanent,
+}
+
+impl TokenDuration {
+    /// The name Reddit uses for this duration.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Temporary => "temporary",
+            Self::Permanent => "permanent",
+        }
+    }
+}
+
+/// The OAuth2 code flow in two steps, for when you handle the redirect yourself, e.g. in a web application.
+/// See [`crate::reddit::Reddit::perform_code_flow`] (requires the 'code_flow' feature) for a version that does everything for you, for installed apps.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// use snew::auth::{CodeFlow, Scope, TokenDuration};
+///
+/// let mut flow = CodeFlow::new("client_id", "https://example.com/reddit/callback");
+/// // Only for 'web app' type applications.
+/// flow.client_secret = Some(String::from("client_secret"));
+///
+/// // Generate a random state, and remember it for the redirect.
+/// let url = flow.authorization_url("random_state", &[Scope::Identity], TokenDuration::Permanent);
+///
+/// // Send the user to `url`. Reddit redirects them back to the redirect URI with `state` and `code` parameters.
+/// // Check that the state matches, then:
+/// let user_auth = flow.exchange_code("code", "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeFlow {
+    pub client_id: String,
+    /// The secret of 'web app' type applications. Installed apps don't have one.
+    pub client_secret: Option<String>,
+    /// Must match the redirect URI of your application exactly.
+    pub redirect_uri: String,
+}
+
+impl CodeFlow {
+    pub fn new(client_id: impl ToString, redirect_uri: impl ToString) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_string(),
+        }
+    }
+
+    /// The URL to send the user to, where they can accept that you use Reddit on their behalf.
+    /// `state` is sent back to the redirect URI unchanged, it should be random and you should check it matches.
+    pub fn authorization_url(
+        &self,
+        state: &str,
+        scopes: &[Scope],
+        duration: TokenDuration,
+    ) -> String {
+        let mut url = Url::parse("https://www.reddit.com/api/v1/authorize").unwrap();
+
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("state", state)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("duration", duration.as_str())
+            .append_pair("scope", &Scope::join(scopes));
+
+        url.into()
+    }
+
+    /// Trade the code Reddit sent to the redirect URI for a token. Each code can only be used once.
+    /// Use the same user agent as for the [`crate::reddit::Reddit`] the authenticator is meant for.
+    pub fn exchange_code(
+        &self,
+        code: &str,
+        user_agent: impl IntoUserAgent,
+    ) -> Result<UserAuthenticator> {
+        let response = AuthenticatedClient::make_client(&user_agent.into_user_agent()?)?
+            .post("https://www.reddit.com/api/v1/access_token")
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.redirect_uri),
+            ])
+            .basic_auth(&self.client_id, self.client_secret.as_ref())
+            .send()?;
+
+        let authenticator =
+            UserAuthenticator::from_token(&self.client_id, parse_response(response)?.into());
+
+        Ok(match &self.client_secret {
+            Some(client_secret) => authenticator.with_client_secret(client_secret),
+            None => authenticator,
+        })
+    }
+}
+
+/// Somewhere to persist a [`Token`], so it survives e.g. an application restart.
+/// [`UserAuthenticator`] reads from this when created with [`UserAuthenticator::from_store`], and writes to it every time the token is refreshed.
+pub trait TokenStore: std::fmt::Debug + Send + Sync {
+    /// Load the stored token. Returns None if nothing has been stored yet.
+    fn load(&self) -> Result<Option<Token>>;
+    /// Store the token, replacing whatever was stored before.
+    fn store(&self, token: &Token) -> Result<()>;
+    /// Remove the stored token, e.g. after it was revoked.
+    fn clear(&self) -> Result<()>;
+}
+
+/// A [`TokenStore`] that keeps the token as JSON in a file. The file holds the refresh token, which grants access to the account,
+/// so on unix it is created readable by the owner only.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<Token>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_str(&fs::read_to_string(
+            &self.path,
+        )?)?))
+    }
+
+    fn store(&self, token: &Token) -> Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        // The refresh token is as good as a password, so only the owner may read it.
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&self.path)?;
+
+        // The mode only applies to new files.
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        file.write_all(serde_json::to_string(token)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A user agent, sent with every request. Reddit recommends the format `<platform>:<app ID>:<version string> (by /u/<reddit username>)`,
+/// which [`UserAgent::builder`] follows. Reddit may rate limit or block generic user agents.
+/// # Usage
+/// ```no_run
+/// use snew::{reddit::Reddit, auth::{ApplicationAuthenticator, UserAgent}};
+///
+/// // E.g. "linux:my_app:v1.0.0 (by /u/my_username) snew/0.1.0"
+/// let user_agent = UserAgent::builder("my_app", "v1.0.0")
+///     .username("my_username")
+///     .build()
+///     .unwrap();
+///
+/// let reddit = Reddit::new(ApplicationAuthenticator::new("client_id"), user_agent).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent(String);
+
+impl UserAgent {
+    /// Build a user agent in the format Reddit recommends. The platform is filled in automatically.
+    pub fn builder(app_id: impl ToString, version: impl ToString) -> UserAgentBuilder {
+        UserAgentBuilder {
+            platform: std::env::consts::OS.to_string(),
+            app_id: app_id.to_string(),
+            version: version.to_string(),
+            username: None,
+        }
+    }
+
+    /// Use `user_agent` exactly as given. Errors if it contains anything but printable ASCII.
+    pub fn custom(user_agent: impl ToString) -> Result<Self> {
+        let user_agent = user_agent.to_string();
+
+        if user_agent.is_empty() || !user_agent.chars().all(|c| (' '..='~').contains(&c)) {
+            return Err(Error::InvalidUserAgent(format!(
+                "'{}' is empty or contains characters other than printable ASCII",
+                user_agent
+            )));
+        }
+
+        Ok(Self(user_agent))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Builds a [`UserAgent`], see [`UserAgent::builder`].
+#[derive(Debug, Clone)]
+pub struct UserAgentBuilder {
+    platform: String,
+    app_id: String,
+    version: String,
+    username: Option<String>,
+}
+
+impl UserAgentBuilder {
+    /// Override the platform, which defaults to the operating system, e.g. 'linux'.
+    pub fn platform(mut self, platform: impl ToString) -> Self {
+        self.platform = platform.to_string();
+        self
+    }
+
+    /// The Reddit username of the developer, so Reddit can contact you.
+    pub fn username(mut self, username: impl ToString) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+
+    /// Check each part, and put them together. snew's own version is appended.
+    pub fn build(self) -> Result<UserAgent> {
+        for (name, part) in &[
+            ("platform", &self.platform),
+            ("app ID", &self.app_id),
+            ("version", &self.version),
+        ] {
+            if part.is_empty() || part.contains(':') {
+                return Err(Error::InvalidUserAgent(format!(
+                    "The {} '{}' is empty or contains ':'",
+                    name, part
+                )));
+            }
+        }
+
+        let mut user_agent = format!("{}:{}:{}", self.platform, self.app_id, self.version);
+
+        if let Some(username) = &self.username {
+            let username = username.trim_start_matches("/u/").trim_start_matches("u/");
+
+            if username.is_empty()
+                || !username
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(Error::InvalidUserAgent(format!(
+                    "'{}' is not a valid Reddit username",
+                    username
+                )));
+            }
+
+            user_agent.push_str(&format!(" (by /u/{})", username));
+        }
+
+        user_agent.push_str(&format!(" snew/{}", env!("CARGO_PKG_VERSION")));
+
+        UserAgent::custom(user_agent)
+    }
+}
+
+/// Something that can be used as a [`UserAgent`]. Strings are used as they are, see [`UserAgent::custom`].
+pub trait IntoUserAgent {
+    fn into_user_agent(self) -> Result<UserAgent>;
+}
+
+impl IntoUserAgent for UserAgent {
+    fn into_user_agent(self) -> Result<UserAgent> {
+        Ok(self)
+    }
+}
+
+impl IntoUserAgent for &str {
+    fn into_user_agent(self) -> Result<UserAgent> {
+        UserAgent::custom(self)
+    }
+}
+
+impl IntoUserAgent for String {
+    fn into_user_agent(self) -> Result<UserAgent> {
+        UserAgent::custom(self)
+    }
+}
+
+impl IntoUserAgent for &String {
+    fn into_user_agent(self) -> Result<UserAgent> {
+        UserAgent::custom(self)
+    }
 }
 
 /// Authenticated interaction with the Reddit API. Use [`crate::reddit::Reddit`] instead.
@@ -25,30 +493,135 @@ pub struct Token {
 pub struct AuthenticatedClient {
     pub(crate) client: Client,
     pub(crate) authenticator: RwLock<Box<dyn Authenticator>>,
+    rate_limit: RwLock<RateLimit>,
 }
 
 impl AuthenticatedClient {
-    pub fn new<T: Authenticator + 'static>(authenticator: T, user_agent: &str) -> Result<Self> {
-        let client = Self::make_client(user_agent)?;
+    pub fn new<T: Authenticator + 'static>(
+        authenticator: T,
+        user_agent: impl IntoUserAgent,
+    ) -> Result<Self> {
+        let client = Self::make_client(&user_agent.into_user_agent()?)?;
+
+        // A token that is still valid, e.g. a stored one, is used as is.
+        let has_valid_token = authenticator
+            .token()
+            .filter(|token| !token.is_expired())
+            .is_some();
 
-        authenticator.login(&client)?;
+        if !has_valid_token {
+            authenticator.login(&client)?;
+        }
 
         Ok(Self {
             authenticator: RwLock::new(Box::new(authenticator) as Box<dyn Authenticator>),
             client,
+            rate_limit: RwLock::new(RateLimit::default()),
         })
     }
 
     pub fn set_authenticator<T: Authenticator + 'static>(&self, authenticator: T) {
         *self.authenticator.write().unwrap() = Box::new(authenticator);
+        *self.rate_limit.write().unwrap() = RateLimit::default();
+    }
+
+    /// The rate limit budget, as of the last request.
+    pub fn rate_limit(&self) -> RateLimit {
+        *self.rate_limit.read().unwrap()
+    }
+
+    /// Fail early if the current authenticator is not logged in as a user.
+    pub(crate) fn require_login(&self) -> Result<()> {
+        if self.authenticator.read()?.is_logged_in() {
+            Ok(())
+        } else {
+            Err(Error::NotLoggedInError)
+        }
+    }
+
+    /// Fail early if the current token is known not to have `scope`.
+    /// Passes if there is no token yet, the request itself will tell.
+    pub(crate) fn require_scope(&self, scope: Scope) -> Result<()> {
+        match self.authenticator.read()?.token() {
+            Some(token) if !token.has_scope(scope) => Err(Error::MissingScope(scope)),
+            _ => Ok(()),
+        }
     }
 
     /// Make a get request to `url`
     /// Errors if the status code was unexpected, the client cannot re-initialize or make the request, or if the authentication fails.
     pub(crate) fn get<Q: Serialize>(&self, url: &str, queries: Option<&Q>) -> Result<Response> {
+        self.request(|client| {
+            let request = client.get(url);
+
+            if let Some(queries) = queries {
+                request.query(queries)
+            } else {
+                request
+            }
+        })
+    }
+
+    /// Make a post request to `url`, with `form` as the body.
+    /// Errors the same way as [`Self::get`].
+    pub(crate) fn post<F: Serialize + ?Sized>(&self, url: &str, form: &F) -> Result<Response> {
+        self.request(|client| client.post(url).form(form))
+    }
+
+    /// Make a put request to `url`, with `form` as the body.
+    /// Errors the same way as [`Self::get`].
+    pub(crate) fn put<F: Serialize + ?Sized>(&self, url: &str, form: &F) -> Result<Response> {
+        self.request(|client| client.put(url).form(form))
+    }
+
+    /// Make a delete request to `url`.
+    /// Errors the same way as [`Self::get`].
+    pub(crate) fn delete(&self, url: &str) -> Result<Response> {
+        self.request(|client| client.delete(url))
+    }
+
+    /// Make a post request to an endpoint that takes `api_type=json`, which must be part of `form`.
+    /// Errors with [`Error::ApiError`] if Reddit returns any errors, otherwise returns the `json` object of the response.
+    pub(crate) fn post_api<F: Serialize + ?Sized>(
+        &self,
+        url: &str,
+        form: &F,
+    ) -> Result<serde_json::Value> {
+        let mut response: serde_json::Value = serde_json::from_str(&self.post(url, form)?.text()?)?;
+        let json = response["json"].take();
+
+        // Errors take the form [code, message, field].
+        if let Some(errors) = json["errors"].as_array() {
+            if !errors.is_empty() {
+                return Err(Error::ApiError(
+                    errors
+                        .iter()
+                        .map(|error| {
+                            error
+                                .as_array()
+                                .map(|parts| {
+                                    parts
+                                        .iter()
+                                        .filter_map(|part| part.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join(": ")
+                                })
+                                .unwrap_or_else(|| error.to_string())
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ));
+            }
+        }
+
+        Ok(json)
+    }
+
+    // Makes the request, refreshing the token and trying again if it was rejected.
+    fn request<B: Fn(&Client) -> RequestBuilder>(&self, build: B) -> Result<Response> {
         // Make one request
         if let Some(token) = &self.authenticator.read().unwrap().token() {
-            let response = self.make_request(&self.client, token, url, queries)?;
+            let response = self.make_request(&self.client, token, &build)?;
 
             if self.check_auth(&response)? {
                 return Ok(response);
@@ -59,10 +632,13 @@ impl AuthenticatedClient {
         self.authenticator.read().unwrap().login(&self.client)?;
 
         if let Some(ref token) = self.authenticator.read().unwrap().token() {
-            let response = self.make_request(&self.client, token, url, queries)?;
+            let response = self.make_request(&self.client, token, &build)?;
 
-            if response.status() == StatusCode::OK {
+            if response.status().is_success() {
                 Ok(response)
+            } else if response.status() == StatusCode::FORBIDDEN {
+                // The token is fine, the user just isn't allowed to do this.
+                Err(Error::PermissionError)
             } else {
                 // Still not authenticated correctly
                 Err(Error::AuthenticationError(String::from(
@@ -75,37 +651,52 @@ impl AuthenticatedClient {
         }
     }
 
-    // Checks queries and makes the actual web request
-    fn make_request<Q: Serialize>(
+    // Adds the token and makes the actual web request
+    fn make_request<B: Fn(&Client) -> RequestBuilder>(
         &self,
         client: &Client,
         token: &Token,
-        url: &str,
-        queries: Option<&Q>,
+        build: &B,
     ) -> Result<Response> {
         let mut authorization = HeaderValue::from_str(&format!("bearer {}", token.access_token))?;
 
         authorization.set_sensitive(true);
 
-        if let Some(queries) = queries {
-            Ok(client
-                .get(url)
-                .header(AUTHORIZATION, authorization)
-                .query(queries)
-                .send()?)
-        } else {
-            Ok(client
-                .get(url)
-                .header(AUTHORIZATION, authorization)
-                .send()?)
+        let response = build(client).header(AUTHORIZATION, authorization).send()?;
+
+        self.update_rate_limit(&response)?;
+
+        Ok(response)
+    }
+
+    // Keep track of the rate limit headers Reddit sends with every response.
+    fn update_rate_limit(&self, response: &Response) -> Result<()> {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<f64>().ok())
+        };
+
+        if let Some(remaining) = header("x-ratelimit-remaining") {
+            *self.rate_limit.write()? = RateLimit {
+                remaining: Some(remaining),
+                used: header("x-ratelimit-used").map(|used| used as u32),
+                reset: header("x-ratelimit-reset")
+                    .map(|reset| Instant::now() + Duration::from_secs(reset as u64)),
+            };
         }
+
+        Ok(())
     }
 
     // Checks that the response is OK. Errors if status code is not expected.
     fn check_auth(&self, response: &Response) -> Result<bool> {
         let status = response.status();
 
-        if status == StatusCode::OK {
+        // Some endpoints answer with e.g. 201 Created.
+        if status.is_success() {
             Ok(true)
         } else if status == StatusCode::FORBIDDEN || status == StatusCode::UNAUTHORIZED {
             Ok(false)
@@ -118,14 +709,35 @@ impl AuthenticatedClient {
     }
 
     // Make a reqwest client with user_agent set as a default header.
-    fn make_client(user_agent: &str) -> Result<Client> {
+    pub(crate) fn make_client(user_agent: &UserAgent) -> Result<Client> {
         Ok(Client::builder()
-            .user_agent(user_agent)
+            .user_agent(user_agent.as_str())
             .gzip(true)
             .build()?)
     }
 }
 
+/// How many requests are left before Reddit starts rate limiting, as reported in the headers of the last response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimit {
+    /// Requests left in this period. None if no request has been made yet.
+    pub remaining: Option<f64>,
+    /// Requests used in this period.
+    pub used: Option<u32>,
+    /// When the period ends, and the budget is reset.
+    pub reset: Option<Instant>,
+}
+
+impl RateLimit {
+    /// Requests left right now. None if unknown, or the period has ended, in which case the full budget is available again.
+    pub fn remaining_now(&self) -> Option<f64> {
+        match self.reset {
+            Some(reset) if reset <= Instant::now() => None,
+            _ => self.remaining,
+        }
+    }
+}
+
 /// Login credentials
 #[derive(Debug, Clone)]
 pub struct Credentials {
@@ -162,24 +774,59 @@ pub trait Authenticator: std::fmt::Debug + Send + Sync {
     fn is_logged_in(&self) -> bool;
     /// Return a refresh token, if one exists.
     fn refresh_token(&self) -> Option<String>;
+    /// Revoke the tokens so they can no longer be used, and forget them. Revoking a refresh token also revokes the access tokens it was used to get.
+    /// Errors by default, so an authenticator that doesn't implement this can't be mistaken for logged out.
+    fn revoke(&self, _client: &Client) -> Result<()> {
+        Err(Error::AuthenticationError(String::from(
+            "This authenticator does not support revoking its tokens",
+        )))
+    }
+}
+
+// So a boxed authenticator, e.g. from [`crate::config::Config::authenticator`], can be passed to [`crate::reddit::Reddit::new`].
+impl<A: Authenticator + ?Sized> Authenticator for Box<A> {
+    fn login(&self, client: &Client) -> Result<()> {
+        (**self).login(client)
+    }
+
+    fn token(&self) -> Option<Token> {
+        (**self).token()
+    }
+
+    fn is_logged_in(&self) -> bool {
+        (**self).is_logged_in()
+    }
+
+    fn refresh_token(&self) -> Option<String> {
+        (**self).refresh_token()
+    }
+
+    fn revoke(&self, client: &Client) -> Result<()> {
+        (**self).revoke(client)
+    }
 }
 
 /// Authenticate on behalf of a user. Use this if you're creating a application that others will use, e.g. a desktop app.
 #[derive(Debug)]
 pub struct UserAuthenticator {
-    // Duplicated because meh
-    refresh_token: String,
+    // Duplicated because meh. None for temporary tokens, or after revoking.
+    refresh_token: RwLock<Option<String>>,
     token: RwLock<Option<Token>>,
     /// Needed to get an access token, even if we have refresh_token
     client_id: String,
+    /// Only 'web app' type applications have a secret.
+    client_secret: Option<String>,
+    store: Option<Box<dyn TokenStore>>,
 }
 
 impl UserAuthenticator {
     pub fn new(refresh_token: impl ToString, client_id: impl ToString) -> Self {
         Self {
-            refresh_token: refresh_token.to_string(),
+            refresh_token: RwLock::new(Some(refresh_token.to_string())),
             token: RwLock::new(None),
             client_id: client_id.to_string(),
+            client_secret: None,
+            store: None,
         }
     }
 
@@ -189,25 +836,108 @@ impl UserAuthenticator {
         token: Token,
     ) -> Self {
         Self {
-            refresh_token: refresh_token.to_string(),
+            refresh_token: RwLock::new(Some(refresh_token.to_string())),
+            token: RwLock::new(Some(token)),
+            client_id: client_id.to_string(),
+            client_secret: None,
+            store: None,
+        }
+    }
+
+    /// Create an authenticator from a token you got from the code flow.
+    /// If the token has no refresh token, i.e. it was requested with [`TokenDuration::Temporary`], it can not be refreshed once it expires.
+    pub fn from_token(client_id: impl ToString, token: Token) -> Self {
+        Self {
+            refresh_token: RwLock::new(token.refresh_token.clone()),
             token: RwLock::new(Some(token)),
             client_id: client_id.to_string(),
+            client_secret: None,
+            store: None,
+        }
+    }
+
+    /// Create an authenticator from a previously stored token.
+    /// Returns None if the store is empty, or the stored token has no refresh token, in which case you need to go through the code flow again.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// use snew::auth::{FileTokenStore, UserAuthenticator};
+    ///
+    /// if let Some(user_auth) = UserAuthenticator::from_store("client_id", FileTokenStore::new("token.json"))? {
+    ///     // Logged in, without bothering the user.
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_store<S: TokenStore + 'static>(
+        client_id: impl ToString,
+        store: S,
+    ) -> Result<Option<Self>> {
+        let token = match store.load()? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        if token.refresh_token.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            store: Some(Box::new(store)),
+            ..Self::from_token(client_id, token)
+        }))
+    }
+
+    /// Use `client_secret` when refreshing the token. Required for 'web app' type applications.
+    pub fn with_client_secret(mut self, client_secret: impl ToString) -> Self {
+        self.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    /// Persist the token to `store` now, and every time it is refreshed.
+    pub fn with_store<S: TokenStore + 'static>(mut self, store: S) -> Result<Self> {
+        if let Some(token) = self.token.get_mut()? {
+            if let Some(refresh_token) = self.refresh_token.get_mut()? {
+                token.refresh_token = Some(refresh_token.clone());
+            }
+            store.store(token)?;
         }
+
+        self.store = Some(Box::new(store));
+
+        Ok(self)
     }
 }
 
 impl Authenticator for UserAuthenticator {
     fn login(&self, client: &Client) -> Result<()> {
+        let refresh_token = self.refresh_token.read()?.clone().ok_or_else(|| {
+            Error::AuthenticationError(String::from(
+                "The token expired, and there is no refresh token to get a new one. Use TokenDuration::Permanent to get a refresh token.",
+            ))
+        })?;
+
         let response = client
             .post("https://www.reddit.com/api/v1/access_token")
             .query(&[
                 ("grant_type", "refresh_token"),
-                ("refresh_token", &self.refresh_token),
+                ("refresh_token", &refresh_token),
             ])
-            .basic_auth(&self.client_id, None::<String>)
+            .basic_auth(&self.client_id, self.client_secret.as_ref())
             .send()?;
 
-        *self.token.write()? = Some(parse_response(response)?.into());
+        let mut token: Token = parse_response(response)?.into();
+
+        // Reddit usually doesn't send the refresh token again, but keep it with the token so the store has everything needed.
+        if token.refresh_token.is_none() {
+            token.refresh_token = Some(refresh_token);
+        }
+
+        if let Some(store) = &self.store {
+            store.store(&token)?;
+        }
+
+        *self.token.write()? = Some(token);
 
         Ok(())
     }
@@ -221,16 +951,124 @@ impl Authenticator for UserAuthenticator {
     }
 
     fn refresh_token(&self) -> Option<String> {
-        Some(self.refresh_token.clone())
+        self.refresh_token.read().unwrap().clone()
+    }
+
+    fn revoke(&self, client: &Client) -> Result<()> {
+        let refresh_token = self.refresh_token.read()?.clone();
+
+        if let Some(refresh_token) = refresh_token {
+            revoke_token(
+                client,
+                &self.client_id,
+                self.client_secret.as_deref(),
+                &refresh_token,
+                "refresh_token",
+            )?;
+        } else if let Some(token) = self.token.read()?.as_ref() {
+            revoke_token(
+                client,
+                &self.client_id,
+                self.client_secret.as_deref(),
+                &token.access_token,
+                "access_token",
+            )?;
+        }
+
+        *self.refresh_token.write()? = None;
+        *self.token.write()? = None;
+
+        if let Some(store) = &self.store {
+            store.clear()?;
+        }
+
+        Ok(())
     }
 }
 
+/// Provides two-factor codes for [`ScriptAuthenticator::with_two_factor`].
+pub enum TwoFactor {
+    /// Called for a code every time the token is requested.
+    Callback(Box<dyn Fn() -> String + Send + Sync>),
+    /// Generate codes from the base32 encoded shared secret, the one shown when setting up two-factor authentication.
+    #[cfg(feature = "totp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "totp")))]
+    Secret(String),
+}
+
+impl TwoFactor {
+    pub fn callback<F: Fn() -> String + Send + Sync + 'static>(callback: F) -> Self {
+        Self::Callback(Box::new(callback))
+    }
+
+    #[cfg(feature = "totp")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "totp")))]
+    pub fn secret(secret: impl ToString) -> Self {
+        Self::Secret(secret.to_string())
+    }
+
+    /// The current code.
+    pub fn code(&self) -> Result<String> {
+        match self {
+            Self::Callback(callback) => Ok(callback()),
+            #[cfg(feature = "totp")]
+            Self::Secret(secret) => totp(secret, now()),
+        }
+    }
+}
+
+impl std::fmt::Debug for TwoFactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Callback(_) => f.write_str("Callback"),
+            // Don't leak the secret.
+            #[cfg(feature = "totp")]
+            Self::Secret(_) => f.write_str("Secret"),
+        }
+    }
+}
+
+// A 6 digit TOTP code (RFC 6238) for `time`, in seconds since the UNIX epoch, with the default 30 second step.
+#[cfg(feature = "totp")]
+pub(crate) fn totp(secret: &str, time: u64) -> Result<String> {
+    use hmac::{Hmac, Mac, NewMac};
+    use sha1::Sha1;
+
+    let secret: String = secret
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=')
+        .collect::<String>()
+        .to_uppercase();
+
+    let key =
+        base32::decode(base32::Alphabet::RFC4648 { padding: false }, &secret).ok_or_else(|| {
+            Error::AuthenticationError(String::from("The two-factor secret is not valid base32"))
+        })?;
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(&key)
+        .map_err(|_| Error::AuthenticationError(String::from("The two-factor secret is empty")))?;
+    mac.update(&(time / 30).to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Dynamic truncation
+    let offset = (hash[hash.len() - 1] & 0xf) as usize;
+    let code = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+
+    Ok(format!("{:06}", code % 1_000_000))
+}
+
 /// Authenticator for Script applications, e.g. bots or other apps that you control.
 /// This includes username and password, which means you are logged in, and can perform actions such as voting.
 /// See also reddit OAuth API docs.
 #[derive(Debug)]
 pub struct ScriptAuthenticator {
     creds: Credentials,
+    two_factor: Option<TwoFactor>,
     token: RwLock<Option<Token>>,
 }
 
@@ -238,20 +1076,45 @@ impl ScriptAuthenticator {
     pub fn new(creds: Credentials) -> Self {
         Self {
             creds,
+            two_factor: None,
             token: RwLock::new(None),
         }
     }
+
+    /// Log in to an account with two-factor authentication enabled. A new code is generated every time the token is refreshed.
+    /// # Usage
+    /// ```no_run
+    /// use snew::auth::{ScriptAuthenticator, Credentials, TwoFactor};
+    ///
+    /// let script_auth = ScriptAuthenticator::new(Credentials::new(
+    ///     "client_id",
+    ///     "client_secret",
+    ///     "username",
+    ///     "password",
+    /// ))
+    /// .with_two_factor(TwoFactor::callback(|| String::from("123456")));
+    /// ```
+    pub fn with_two_factor(mut self, two_factor: TwoFactor) -> Self {
+        self.two_factor = Some(two_factor);
+        self
+    }
 }
 
 impl Authenticator for ScriptAuthenticator {
     fn login(&self, client: &Client) -> Result<()> {
+        // Reddit expects the two-factor code appended to the password.
+        let password = match &self.two_factor {
+            Some(two_factor) => format!("{}:{}", self.creds.password, two_factor.code()?),
+            None => self.creds.password.clone(),
+        };
+
         // Make the request for the access token.
         let response = client
             .post("https://www.reddit.com/api/v1/access_token")
             .query(&[
                 ("grant_type", "password"),
                 ("username", &self.creds.username),
-                ("password", &self.creds.password),
+                ("password", &password),
             ])
             .basic_auth(&self.creds.client_id, Some(&self.creds.client_secret))
             .send()?;
@@ -272,6 +1135,20 @@ impl Authenticator for ScriptAuthenticator {
     fn refresh_token(&self) -> Option<String> {
         None
     }
+
+    fn revoke(&self, client: &Client) -> Result<()> {
+        if let Some(token) = self.token.write()?.take() {
+            revoke_token(
+                client,
+                &self.creds.client_id,
+                Some(&self.creds.client_secret),
+                &token.access_token,
+                "access_token",
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Anonymous application authentication.
@@ -279,14 +1156,21 @@ impl Authenticator for ScriptAuthenticator {
 #[derive(Debug)]
 pub struct ApplicationAuthenticator {
     client_id: String,
+    device_id: String,
     token: RwLock<Option<Token>>,
 }
 
 impl ApplicationAuthenticator {
     pub fn new(client_id: impl ToString) -> Self {
+        Self::with_device_id(client_id, "DO_NOT_TRACK_THIS_DEVICE")
+    }
+
+    /// Use a device id of your own, which Reddit uses to tell installations apart. It should be unique per device, and 20-30 characters long.
+    pub fn with_device_id(client_id: impl ToString, device_id: impl ToString) -> Self {
         Self {
             token: RwLock::new(None),
             client_id: client_id.to_string(),
+            device_id: device_id.to_string(),
         }
     }
 }
@@ -302,7 +1186,7 @@ impl Authenticator for ApplicationAuthenticator {
                     "grant_type",
                     "https://oauth.reddit.com/grants/installed_client",
                 ),
-                ("device_id", "DO_NOT_TRACK_THIS_DEVICE"),
+                ("device_id", &self.device_id),
             ])
             .send()?;
 
@@ -320,6 +1204,104 @@ impl Authenticator for ApplicationAuthenticator {
     fn refresh_token(&self) -> Option<String> {
         None
     }
+
+    fn revoke(&self, client: &Client) -> Result<()> {
+        if let Some(token) = self.token.write()?.take() {
+            revoke_token(
+                client,
+                &self.client_id,
+                None,
+                &token.access_token,
+                "access_token",
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Application-only authentication for confidential clients, i.e. 'web app' and 'script' type applications, which have a secret.
+/// Like [`ApplicationAuthenticator`], you will not be logged in as some user. Use this for e.g. a server-side crawler.
+#[derive(Debug)]
+pub struct ClientCredentialsAuthenticator {
+    client_id: String,
+    client_secret: String,
+    token: RwLock<Option<Token>>,
+}
+
+impl ClientCredentialsAuthenticator {
+    pub fn new(client_id: impl ToString, client_secret: impl ToString) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            token: RwLock::new(None),
+        }
+    }
+}
+
+impl Authenticator for ClientCredentialsAuthenticator {
+    fn login(&self, client: &Client) -> Result<()> {
+        // Make the request for the access token.
+        let response = client
+            .post("https://www.reddit.com/api/v1/access_token")
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .query(&[("grant_type", "client_credentials")])
+            .send()?;
+
+        *self.token.write()? = Some(parse_response(response)?.into());
+        Ok(())
+    }
+
+    fn token(&self) -> Option<Token> {
+        self.token.read().unwrap().clone()
+    }
+
+    fn is_logged_in(&self) -> bool {
+        false
+    }
+
+    fn refresh_token(&self) -> Option<String> {
+        None
+    }
+
+    fn revoke(&self, client: &Client) -> Result<()> {
+        if let Some(token) = self.token.write()?.take() {
+            revoke_token(
+                client,
+                &self.client_id,
+                Some(&self.client_secret),
+                &token.access_token,
+                "access_token",
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Revoke a single token. `token_type_hint` is either access_token or refresh_token.
+fn revoke_token(
+    client: &Client,
+    client_id: &str,
+    client_secret: Option<&str>,
+    token: &str,
+    token_type_hint: &str,
+) -> Result<()> {
+    let response = client
+        .post("https://www.reddit.com/api/v1/revoke_token")
+        .basic_auth(client_id, client_secret)
+        .form(&[("token", token), ("token_type_hint", token_type_hint)])
+        .send()?;
+
+    // Reddit returns 204 No Content, even if the token was already invalid.
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(Error::AuthenticationError(format!(
+            "Failed to revoke token, Reddit returned: {}",
+            response.status()
+        )))
+    }
 }
 
 pub(crate) fn parse_response(response: Response) -> Result<TokenJson> {
@@ -366,10 +1348,20 @@ impl From<TokenJson> for Token {
             expires_in: token.expires_in,
             scope: token.scope,
             token_type: token.token_type,
+            refresh_token: token.refresh_token,
+            issued_at: now(),
         }
     }
 }
 
+// Seconds since the UNIX epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
 // Reddit can return 200 OK even if the credentials are wrong, in which case it will include one field, "error": "message"
 #[derive(Deserialize)]
 struct OkButError {
@@ -0,0 +1,179 @@
+//! Load credentials and settings from environment variables or a config file, instead of hard-coding them.
+use crate::{
+    auth::{
+        ApplicationAuthenticator, Authenticator, ClientCredentialsAuthenticator, Credentials,
+        ScriptAuthenticator, UserAgent, UserAuthenticator,
+    },
+    reddit::{Error, Reddit, Result},
+};
+
+use serde::Deserialize;
+
+use std::env;
+
+/// Everything needed to connect to Reddit. All fields are optional, which authenticator is used depends on which are set, see [`Config::authenticator`].
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// use snew::config::Config;
+///
+/// // Reads REDDIT_CLIENT_ID, REDDIT_CLIENT_SECRET, REDDIT_USERNAME, REDDIT_PASSWORD etc.
+/// let reddit = Config::from_env().reddit()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Config {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub refresh_token: Option<String>,
+    /// The base32 secret for accounts with two-factor authentication. Requires the 'totp' feature.
+    pub two_factor_secret: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl Config {
+    /// Read the config from the environment variables `REDDIT_CLIENT_ID`, `REDDIT_CLIENT_SECRET`, `REDDIT_USERNAME`, `REDDIT_PASSWORD`,
+    /// `REDDIT_REFRESH_TOKEN`, `REDDIT_TWO_FACTOR_SECRET` and `REDDIT_USER_AGENT`.
+    pub fn from_env() -> Self {
+        Self {
+            client_id: env::var("REDDIT_CLIENT_ID").ok(),
+            client_secret: env::var("REDDIT_CLIENT_SECRET").ok(),
+            username: env::var("REDDIT_USERNAME").ok(),
+            password: env::var("REDDIT_PASSWORD").ok(),
+            refresh_token: env::var("REDDIT_REFRESH_TOKEN").ok(),
+            two_factor_secret: env::var("REDDIT_TWO_FACTOR_SECRET").ok(),
+            user_agent: env::var("REDDIT_USER_AGENT").ok(),
+        }
+    }
+
+    /// Read the `profile` section of a TOML config file. Fields missing from the profile are taken from the `[default]` section, if there is one.
+    /// ```toml
+    /// [default]
+    /// user_agent = "linux:mybots:v1.0.0 (by /u/someone)"
+    ///
+    /// [greeter]
+    /// client_id = "client_id"
+    /// client_secret = "client_secret"
+    /// username = "greeter_bot"
+    /// password = "password"
+    /// ```
+    #[cfg(feature = "config_file")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "config_file")))]
+    pub fn from_file(path: impl AsRef<std::path::Path>, profile: &str) -> Result<Self> {
+        Self::from_toml(&std::fs::read_to_string(path)?, profile)
+    }
+
+    /// Like [`Config::from_file`], with the contents of the file.
+    #[cfg(feature = "config_file")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "config_file")))]
+    pub fn from_toml(toml: &str, profile: &str) -> Result<Self> {
+        let mut profiles: std::collections::HashMap<String, Self> = toml::from_str(toml)
+            .map_err(|error| Error::ConfigError(format!("Invalid config file: {}", error)))?;
+
+        let config = profiles.remove(profile).ok_or_else(|| {
+            Error::ConfigError(format!("No profile named '{}' in config file", profile))
+        })?;
+
+        Ok(match profiles.remove("default") {
+            Some(default) => config.or(default),
+            None => config,
+        })
+    }
+
+    /// Fill the fields missing from this config with the ones from `other`.
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            client_id: self.client_id.or(other.client_id),
+            client_secret: self.client_secret.or(other.client_secret),
+            username: self.username.or(other.username),
+            password: self.password.or(other.password),
+            refresh_token: self.refresh_token.or(other.refresh_token),
+            two_factor_secret: self.two_factor_secret.or(other.two_factor_secret),
+            user_agent: self.user_agent.or(other.user_agent),
+        }
+    }
+
+    /// Script credentials. Errors if any of client id, client secret, username or password is missing.
+    pub fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials::new(
+            required(&self.client_id, "client_id")?,
+            required(&self.client_secret, "client_secret")?,
+            required(&self.username, "username")?,
+            required(&self.password, "password")?,
+        ))
+    }
+
+    /// The user agent, or one in the format Reddit recommends if none is set.
+    pub fn user_agent(&self) -> Result<UserAgent> {
+        match &self.user_agent {
+            Some(user_agent) => UserAgent::custom(user_agent),
+            None => {
+                let builder = UserAgent::builder("snew", env!("CARGO_PKG_VERSION"));
+
+                match &self.username {
+                    Some(username) => builder.username(username),
+                    None => builder,
+                }
+                .build()
+            }
+        }
+    }
+
+    /// The authenticator that fits the fields that are set:
+    /// * Username and password: [`ScriptAuthenticator`], with two-factor authentication if there is a secret for it.
+    /// * Refresh token: [`UserAuthenticator`].
+    /// * Client secret: [`ClientCredentialsAuthenticator`].
+    /// * Only client id: [`ApplicationAuthenticator`].
+    pub fn authenticator(&self) -> Result<Box<dyn Authenticator>> {
+        let client_id = required(&self.client_id, "client_id")?;
+
+        if self.username.is_some() || self.password.is_some() {
+            let script_auth = ScriptAuthenticator::new(self.credentials()?);
+
+            Ok(Box::new(match &self.two_factor_secret {
+                Some(secret) => script_auth.with_two_factor(two_factor(secret)?),
+                None => script_auth,
+            }))
+        } else if let Some(refresh_token) = &self.refresh_token {
+            let user_auth = UserAuthenticator::new(refresh_token, client_id);
+
+            Ok(Box::new(match &self.client_secret {
+                Some(client_secret) => user_auth.with_client_secret(client_secret),
+                None => user_auth,
+            }))
+        } else if let Some(client_secret) = &self.client_secret {
+            Ok(Box::new(ClientCredentialsAuthenticator::new(
+                client_id,
+                client_secret,
+            )))
+        } else {
+            Ok(Box::new(ApplicationAuthenticator::new(client_id)))
+        }
+    }
+
+    /// Connect to Reddit with [`Config::authenticator`] and [`Config::user_agent`].
+    pub fn reddit(&self) -> Result<Reddit> {
+        Reddit::new(self.authenticator()?, self.user_agent()?)
+    }
+}
+
+fn required<'a>(field: &'a Option<String>, name: &str) -> Result<&'a String> {
+    field
+        .as_ref()
+        .ok_or_else(|| Error::ConfigError(format!("Missing {} in config", name)))
+}
+
+#[cfg(feature = "totp")]
+fn two_factor(secret: &str) -> Result<crate::auth::TwoFactor> {
+    Ok(crate::auth::TwoFactor::secret(secret))
+}
+
+#[cfg(not(feature = "totp"))]
+fn two_factor(_: &str) -> Result<crate::auth::TwoFactor> {
+    Err(Error::ConfigError(String::from(
+        "two_factor_secret is set, but snew was built without the 'totp' feature",
+    )))
+}
@@ -0,0 +1,354 @@
+//! Post and user flair: templates, assigning flair, and letting users pick their own.
+use crate::{
+    auth::Scope,
+    reddit::{Error, Result},
+    things::{username, Subreddit},
+};
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+/// # Flair
+/// Managing templates and the flair of others is only allowed for moderators with the 'flair' permission.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// use snew::flair::{Flair, FlairTemplateOptions, FlairType};
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+/// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+///
+/// let rust = reddit.subreddit("rust");
+/// let template = rust.create_flair_template(FlairType::Link, &FlairTemplateOptions::new("Tooling"))?;
+///
+/// for post in rust.new().take(100) {
+///     let post = post?;
+///
+///     if post.title.contains("cargo") {
+///         post.set_flair(&Flair::template(&template.id))?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+impl Subreddit {
+    /// The flair templates posts can have.
+    pub fn link_flair_templates(&self) -> Result<Vec<FlairTemplate>> {
+        self.flair_templates("link_flair_v2")
+    }
+
+    /// The flair templates users can have.
+    pub fn user_flair_templates(&self) -> Result<Vec<FlairTemplate>> {
+        self.flair_templates("user_flair_v2")
+    }
+
+    pub fn create_flair_template(
+        &self,
+        flair_type: FlairType,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        self.save_flair_template(flair_type, None, options)
+    }
+
+    /// Replace the template with the id `template_id`.
+    pub fn update_flair_template(
+        &self,
+        flair_type: FlairType,
+        template_id: &str,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        self.save_flair_template(flair_type, Some(template_id), options)
+    }
+
+    pub fn delete_flair_template(&self, template_id: &str) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModFlair)?;
+
+        self.client.post_api(
+            &format!("{}/api/deleteflairtemplate", self.url),
+            &[("flair_template_id", template_id), ("api_type", "json")],
+        )?;
+
+        Ok(())
+    }
+
+    /// Delete all templates of `flair_type`.
+    pub fn clear_flair_templates(&self, flair_type: FlairType) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModFlair)?;
+
+        self.client.post_api(
+            &format!("{}/api/clearflairtemplates", self.url),
+            &[("flair_type", flair_type.as_str()), ("api_type", "json")],
+        )?;
+
+        Ok(())
+    }
+
+    /// Set the flair of `username` in this subreddit.
+    pub fn set_user_flair(&self, username: &str, flair: &Flair) -> Result<()> {
+        self.client.require_scope(Scope::ModFlair)?;
+
+        self.set_flair(flair.request(("name", username)))
+    }
+
+    /// Set the flair of the post with the fullname `post`, see [`crate::things::Post::set_flair`].
+    pub fn set_post_flair(&self, post: &str, flair: &Flair) -> Result<()> {
+        // Either 'modflair', or 'flair' for the author of the post, so the request has to tell.
+        self.set_flair(flair.request(("link", post)))
+    }
+
+    /// Set the flair of many users at once. Each line of `csv` is `username,text,css_class`, an empty text and css class removes the flair.
+    /// Reddit takes 100 lines per request, larger files are split up. Returns the result of every line.
+    pub fn upload_flair_csv(&self, csv: &str) -> Result<Vec<FlairCsvResult>> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModFlair)?;
+
+        let lines = csv
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>();
+        let mut results = Vec::new();
+
+        for chunk in lines.chunks(100) {
+            let text = self
+                .client
+                .post(
+                    &format!("{}/api/flaircsv", self.url),
+                    &[("flair_csv", chunk.join("\n"))],
+                )?
+                .text()?;
+
+            let chunk_results: Vec<FlairCsvResult> = serde_json::from_str(&text)?;
+            results.extend(chunk_results);
+        }
+
+        Ok(results)
+    }
+
+    /// Set the flair of the logged in user, if the subreddit lets users pick their own.
+    /// Users can only pick a template, with optionally their own text if it is editable. `Flair::default()` removes their flair.
+    pub fn select_flair(&self, flair: &Flair) -> Result<()> {
+        self.client.require_scope(Scope::Flair)?;
+
+        let username = username(&self.client)?;
+
+        self.set_flair(flair.select_request(("name", &username))?)
+    }
+
+    /// Whether the flair of the logged in user is shown in this subreddit.
+    pub fn set_flair_enabled(&self, enabled: bool) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Flair)?;
+
+        self.client.post_api(
+            &format!("{}/api/setflairenabled", self.url),
+            &[
+                ("flair_enabled", enabled.to_string().as_str()),
+                ("api_type", "json"),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn flair_templates(&self, path: &str) -> Result<Vec<FlairTemplate>> {
+        self.client.require_scope(Scope::Flair)?;
+
+        let text = self
+            .client
+            .get(&format!("{}/api/{}", self.url, path), None::<&()>)?
+            .text()?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    fn save_flair_template(
+        &self,
+        flair_type: FlairType,
+        template_id: Option<&str>,
+        options: &FlairTemplateOptions,
+    ) -> Result<FlairTemplate> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModFlair)?;
+
+        let mut form = vec![
+            ("flair_type", flair_type.as_str().to_string()),
+            ("text", options.text.clone()),
+            ("css_class", options.css_class.clone()),
+            ("text_editable", options.text_editable.to_string()),
+            ("mod_only", options.mod_only.to_string()),
+        ];
+
+        if let Some(template_id) = template_id {
+            form.push(("flair_template_id", template_id.to_string()));
+        }
+        if let Some(background_color) = &options.background_color {
+            form.push(("background_color", background_color.clone()));
+        }
+        if let Some(text_color) = &options.text_color {
+            form.push(("text_color", text_color.clone()));
+        }
+
+        let text = self
+            .client
+            .post(&format!("{}/api/flairtemplate_v2", self.url), &form)?
+            .text()?;
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    fn set_flair(&self, (endpoint, form): (&str, Vec<(&str, &str)>)) -> Result<()> {
+        self.client.require_login()?;
+
+        self.client
+            .post_api(&format!("{}/api/{}", self.url, endpoint), &form)?;
+
+        Ok(())
+    }
+}
+
+/// Whether flair belongs to posts or users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlairType {
+    /// Flair of posts.
+    Link,
+    User,
+}
+
+impl FlairType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Link => "LINK_FLAIR",
+            Self::User => "USER_FLAIR",
+        }
+    }
+}
+
+/// A flair template of a subreddit, see [`Subreddit::link_flair_templates`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlairTemplate {
+    pub id: String,
+    pub text: String,
+    #[serde(default)]
+    pub css_class: String,
+    /// Whether whoever gets this flair can change the text.
+    pub text_editable: bool,
+    /// Whether only moderators can assign this flair.
+    pub mod_only: bool,
+    /// E.g. #ff4500, empty if there is none.
+    #[serde(default)]
+    pub background_color: String,
+    /// "dark" or "light".
+    #[serde(default)]
+    pub text_color: String,
+}
+
+/// What a flair template should look like, see [`Subreddit::create_flair_template`].
+#[derive(Debug, Clone)]
+pub struct FlairTemplateOptions {
+    pub text: String,
+    pub css_class: String,
+    pub text_editable: bool,
+    pub mod_only: bool,
+    /// E.g. #ff4500.
+    pub background_color: Option<String>,
+    /// "dark" or "light".
+    pub text_color: Option<String>,
+}
+
+impl FlairTemplateOptions {
+    /// A template with `text`, that anyone can pick, without a css class or colors.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: String::from(text),
+            css_class: String::new(),
+            text_editable: false,
+            mod_only: false,
+            background_color: None,
+            text_color: None,
+        }
+    }
+}
+
+/// Flair to give a post or user. `Flair::default()` removes the flair.
+#[derive(Debug, Clone, Default)]
+pub struct Flair {
+    /// The id of a [`FlairTemplate`].
+    pub template_id: Option<String>,
+    /// Overrides the text of the template, if it is editable.
+    pub text: Option<String>,
+    pub css_class: Option<String>,
+}
+
+impl Flair {
+    /// The flair of a template, with the template's text.
+    pub fn template(template_id: &str) -> Self {
+        Self {
+            template_id: Some(String::from(template_id)),
+            ..Self::default()
+        }
+    }
+
+    /// Flair with just text.
+    pub fn text(text: &str) -> Self {
+        Self {
+            text: Some(String::from(text)),
+            ..Self::default()
+        }
+    }
+
+    // The endpoint and form that give `target` this flair. A template, with optionally some text or css class, goes through
+    // selectflair. Plain text and css class through flair, which removes the flair if neither is set.
+    pub(crate) fn request<'a>(
+        &'a self,
+        target: (&'a str, &'a str),
+    ) -> (&'static str, Vec<(&'a str, &'a str)>) {
+        let mut form = vec![target, ("api_type", "json")];
+
+        if let Some(text) = &self.text {
+            form.push(("text", text));
+        }
+        if let Some(css_class) = &self.css_class {
+            form.push(("css_class", css_class));
+        }
+
+        match &self.template_id {
+            Some(template_id) => {
+                form.push(("flair_template_id", template_id));
+                ("selectflair", form)
+            }
+            None => ("flair", form),
+        }
+    }
+
+    // The endpoint and form that let a user pick this flair for themselves. The flair endpoint needs the 'modflair' scope,
+    // so this always goes through selectflair, which removes the flair without a template.
+    pub(crate) fn select_request<'a>(
+        &'a self,
+        target: (&'a str, &'a str),
+    ) -> Result<(&'static str, Vec<(&'a str, &'a str)>)> {
+        if self.template_id.is_none() && (self.text.is_some() || self.css_class.is_some()) {
+            return Err(Error::InvalidFlair(String::from(
+                "Users can only pick flair from a template. Set the template id, or use Flair::default() to remove the flair.",
+            )));
+        }
+
+        let (_, form) = self.request(target);
+
+        Ok(("selectflair", form))
+    }
+}
+
+/// The result of a line of a flair CSV, see [`Subreddit::upload_flair_csv`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlairCsvResult {
+    pub ok: bool,
+    /// E.g. "added flair for user someone".
+    pub status: String,
+    #[serde(default)]
+    pub errors: HashMap<String, String>,
+    #[serde(default)]
+    pub warnings: HashMap<String, String>,
+}
@@ -0,0 +1,215 @@
+//! The inbox of the logged in user: private messages, comment replies and username mentions.
+use crate::{
+    auth::{AuthenticatedClient, Scope},
+    reddit::{Result, URL},
+    things::{
+        raw::{generic_kind::RawKind, message::RawMessageData},
+        Cursor, Message, Transpose,
+    },
+};
+
+use std::sync::Arc;
+
+/// A handle into the inbox of the logged in user. Use [`crate::reddit::Reddit::inbox()`] to get one.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new(
+/// #    "client_id",
+/// #   "client_secret",
+/// #   "username",
+/// #   "password",
+/// # ));
+/// # let reddit = Reddit::new(
+/// #    script_auth,
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// let inbox = reddit.inbox()?;
+///
+/// inbox.compose("some_user", "Welcome!", "Thanks for contributing.")?;
+///
+/// for mention in inbox.mentions().take(10) {
+///     let mention = mention?;
+///
+///     if mention.new {
+///         println!("{:?} mentioned us: {}", mention.author, mention.body);
+///         mention.mark_read()?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Inbox {
+    client: Arc<AuthenticatedClient>,
+}
+
+impl Inbox {
+    pub(crate) fn create(client: Arc<AuthenticatedClient>) -> Self {
+        Self { client }
+    }
+
+    /// Everything in the inbox.
+    pub fn all(&self) -> MessageFeed {
+        self.messages_at("inbox")
+    }
+
+    /// Everything that hasn't been read yet. Listing these does not mark them as read.
+    pub fn unread(&self) -> MessageFeed {
+        let mut feed = self.messages_at("unread");
+        feed.mark = Some(false);
+        feed
+    }
+
+    /// Private messages only.
+    pub fn messages(&self) -> MessageFeed {
+        self.messages_at("messages")
+    }
+
+    /// Replies to your comments.
+    pub fn comment_replies(&self) -> MessageFeed {
+        self.messages_at("comments")
+    }
+
+    /// Replies to your posts.
+    pub fn post_replies(&self) -> MessageFeed {
+        self.messages_at("selfreply")
+    }
+
+    /// Comments mentioning your username.
+    pub fn mentions(&self) -> MessageFeed {
+        self.messages_at("mentions")
+    }
+
+    /// Private messages you have sent.
+    pub fn sent(&self) -> MessageFeed {
+        self.messages_at("sent")
+    }
+
+    /// Send a private message to the user `to`.
+    pub fn compose(&self, to: &str, subject: &str, body: &str) -> Result<()> {
+        self.send(
+            to.trim_start_matches("/u/").trim_start_matches("u/"),
+            subject,
+            body,
+        )
+    }
+
+    /// Send a private message to the moderators of `subreddit`.
+    pub fn message_moderators(&self, subreddit: &str, subject: &str, body: &str) -> Result<()> {
+        self.send(
+            &format!(
+                "/r/{}",
+                subreddit.trim_start_matches("/r/").trim_start_matches("r/")
+            ),
+            subject,
+            body,
+        )
+    }
+
+    fn send(&self, to: &str, subject: &str, body: &str) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client.post_api(
+            &format!("{}/api/compose", URL),
+            &[
+                ("api_type", "json"),
+                ("to", to),
+                ("subject", subject),
+                ("text", body),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Mark the messages with the given fullnames as read, see [`Message::fullname`].
+    pub fn mark_read(&self, fullnames: &[&str]) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client.post(
+            &format!("{}/api/read_message", URL),
+            &[("id", fullnames.join(","))],
+        )?;
+
+        Ok(())
+    }
+
+    /// Mark the messages with the given fullnames as unread, see [`Message::fullname`].
+    pub fn mark_unread(&self, fullnames: &[&str]) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client.post(
+            &format!("{}/api/unread_message", URL),
+            &[("id", fullnames.join(","))],
+        )?;
+
+        Ok(())
+    }
+
+    /// Mark everything in the inbox as read.
+    pub fn mark_all_read(&self) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client
+            .post(&format!("{}/api/read_all_messages", URL), &())?;
+
+        Ok(())
+    }
+
+    fn messages_at(&self, path: &str) -> MessageFeed {
+        MessageFeed {
+            limit: 100,
+            mark: None,
+            url: format!("{}/message/{}", URL, path),
+            cached_messages: Vec::new(),
+            client: self.client.clone(),
+            cursor: Cursor::default(),
+        }
+    }
+}
+
+/// A set of messages from the inbox, newest first, meant to be iterated over. See [`crate::things::PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct MessageFeed {
+    /// The amount of messages to request from the Reddit API at once, at most 100.
+    pub limit: i32,
+    mark: Option<bool>,
+    url: String,
+    cached_messages: Vec<Message>,
+    client: Arc<AuthenticatedClient>,
+    cursor: Cursor,
+}
+
+impl Iterator for MessageFeed {
+    type Item = Result<Message>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_messages.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::PrivateMessages)?;
+
+            let mut queries = Vec::new();
+
+            if let Some(mark) = self.mark {
+                queries.push((String::from("mark"), mark.to_string()));
+            }
+
+            let children: Vec<RawKind<RawMessageData>> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &queries)?;
+
+            let client = &self.client;
+
+            self.cached_messages.extend(
+                children
+                    .into_iter()
+                    .rev()
+                    .map(|raw| (raw, client.clone()))
+                    .map(From::from),
+            );
+
+            Ok(self.cached_messages.pop())
+        })
+    }
+}
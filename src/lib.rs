@@ -36,21 +36,45 @@
 //!     // do something    
 //! }
 //! ```
-//! # User authentication for installed apps. Requires the 'code_flow' feature.
+//! # Confidential application authentication (not logged in)
+//! For 'web app' and 'script' type applications, which have a secret.
 //! ```no_run
-//! use snew::{reddit::Reddit};
+//! use snew::{reddit::Reddit, auth::ClientCredentialsAuthenticator};
+//!
+//! let app_auth = ClientCredentialsAuthenticator::new("client_id", "client_secret");
+//!
+//! let reddit = Reddit::new(
+//!     app_auth,
+//!     "<Operating system>:snew:v0.1.0 (by u/<reddit username>)").unwrap();
+//! ```
+//! # User authentication for installed apps. Requires the 'code_flow' feature.
+//! ```ignore
+//! use snew::{reddit::{Reddit, CodeFlowOptions}, auth::Scope};
+//! use std::time::Duration;
 //!
+//! let mut options = CodeFlowOptions::new(
+//!     "client_id",
+//!     &[Scope::Identity, Scope::Read],
+//!     "<Operating system>:snew:v0.1.0 (by u/<reddit username>)").unwrap();
 //! // Wait 180 seconds for the user to complete their end of the flow
-//! // Comment out because features
-//! // let user_auth = Reddit::perform_code_flow("client_id", "Great, return to the app now", Some(Duration::from_secs(180)))
+//! options.timeout = Some(Duration::from_secs(180));
 //!
-// ! // let reddit = Reddit::new(
-// ! //    user_auth,
-// ! //    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)").unwrap();
+//! let user_auth = Reddit::perform_code_flow(options).unwrap();
 //!
-//! // for post in reddit.subreddit("rust").new().take(5) {
-//!     // do something    
-//! // }
+//! let reddit = Reddit::new(
+//!     user_auth,
+//!     "<Operating system>:snew:v0.1.0 (by u/<reddit username>)").unwrap();
+//!
+//! for post in reddit.subreddit("rust").new().take(5) {
+//!     // do something
+//! }
+//! ```
+//! # Credentials from the environment or a config file
+//! ```no_run
+//! use snew::config::Config;
+//!
+//! // Picks the authenticator based on what is set, e.g. REDDIT_USERNAME and REDDIT_PASSWORD for script authentication.
+//! let reddit = Config::from_env().reddit().unwrap();
 //! ```
 //! See also [`reddit::Reddit`] for more examples, and how to retrieve your client id and secret.
 // #![deny(clippy::all)]
@@ -63,8 +87,15 @@
 #![deny(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 pub mod auth;
+pub mod config;
 #[cfg(feature = "parse_content")]
 pub mod content;
+pub mod flair;
+pub mod inbox;
+pub mod moderation;
+pub mod modmail;
+pub mod pool;
 pub mod reddit;
+pub mod stream;
 mod tests;
 pub mod things;
@@ -0,0 +1,637 @@
+//! Moderating a subreddit: bans, mutes, approved contributors, moderators and the mod log.
+use crate::{
+    auth::{AuthenticatedClient, Scope},
+    reddit::Result,
+    stream::Stream,
+    things::{
+        raw::{generic_kind::RawKind, listing::RawListing},
+        Cursor, Subreddit, Transpose,
+    },
+};
+
+use serde::Deserialize;
+
+use std::sync::Arc;
+
+/// # Moderation
+/// Only allowed for moderators of the subreddit with the right permissions.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// use snew::moderation::BanOptions;
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+/// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+///
+/// let rust = reddit.subreddit("rust");
+/// let rust_gamedev = reddit.subreddit("rust_gamedev");
+///
+/// // Copy the bans of one subreddit to another.
+/// for banned in rust.banned()? {
+///     let banned = banned?;
+///
+///     let options = BanOptions {
+///         duration: banned.days_left,
+///         note: banned.note,
+///         ..BanOptions::default()
+///     };
+///
+///     rust_gamedev.ban(&banned.name, &options)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+impl Subreddit {
+    /// The users banned from this subreddit.
+    pub fn banned(&self) -> Result<RelatedUserFeed> {
+        self.related_users("banned")
+    }
+
+    /// Ban `username` from this subreddit.
+    pub fn ban(&self, username: &str, options: &BanOptions) -> Result<()> {
+        let duration = options.duration.map(|duration| duration.to_string());
+        let mut form = vec![];
+
+        if let Some(duration) = &duration {
+            form.push(("duration", duration.as_str()));
+        }
+        if let Some(reason) = &options.reason {
+            form.push(("ban_reason", reason));
+        }
+        if let Some(note) = &options.note {
+            form.push(("note", note));
+        }
+        if let Some(message) = &options.message {
+            form.push(("ban_message", message));
+        }
+
+        self.friend(username, "banned", Scope::ModContributors, &form)
+    }
+
+    pub fn unban(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "banned", Scope::ModContributors)
+    }
+
+    /// The users muted in this subreddit, who cannot message the moderators.
+    pub fn muted(&self) -> Result<RelatedUserFeed> {
+        self.related_users("muted")
+    }
+
+    /// Mute `username`, so they cannot message the moderators for 72 hours.
+    pub fn mute(&self, username: &str) -> Result<()> {
+        self.friend(username, "muted", Scope::ModContributors, &[])
+    }
+
+    pub fn unmute(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "muted", Scope::ModContributors)
+    }
+
+    /// The approved contributors, who can post even if the subreddit is restricted or private.
+    pub fn contributors(&self) -> Result<RelatedUserFeed> {
+        self.related_users("contributors")
+    }
+
+    pub fn add_contributor(&self, username: &str) -> Result<()> {
+        self.friend(username, "contributor", Scope::ModContributors, &[])
+    }
+
+    pub fn remove_contributor(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "contributor", Scope::ModContributors)
+    }
+
+    /// The users allowed to edit the wiki.
+    pub fn wiki_contributors(&self) -> Result<RelatedUserFeed> {
+        self.related_users("wikicontributors")
+    }
+
+    pub fn add_wiki_contributor(&self, username: &str) -> Result<()> {
+        self.friend(username, "wikicontributor", Scope::ModContributors, &[])
+    }
+
+    pub fn remove_wiki_contributor(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "wikicontributor", Scope::ModContributors)
+    }
+
+    /// The moderators of this subreddit, with their permissions. Visible to everyone.
+    pub fn moderators(&self) -> Result<Vec<Moderator>> {
+        self.client.require_scope(Scope::Read)?;
+
+        let text = self
+            .client
+            .get(&format!("{}/about/moderators", self.url), None::<&()>)?
+            .text()?;
+
+        let listing: RawListing<Moderator> = serde_json::from_str(&text)?;
+
+        Ok(listing.data.children)
+    }
+
+    /// Invite `username` to moderate this subreddit. An empty `permissions` gives no permissions, use [`ModPermission::All`] for full permissions.
+    pub fn invite_moderator(&self, username: &str, permissions: &[ModPermission]) -> Result<()> {
+        self.friend(
+            username,
+            "moderator_invite",
+            Scope::ModOthers,
+            &[("permissions", &ModPermission::join(permissions))],
+        )
+    }
+
+    /// Take back an invite that hasn't been accepted yet.
+    pub fn revoke_moderator_invite(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "moderator_invite", Scope::ModOthers)
+    }
+
+    /// Change the permissions of a moderator, or of an invite that hasn't been accepted yet if `invite` is true.
+    pub fn set_moderator_permissions(
+        &self,
+        username: &str,
+        permissions: &[ModPermission],
+        invite: bool,
+    ) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModOthers)?;
+
+        let relationship = if invite {
+            "moderator_invite"
+        } else {
+            "moderator"
+        };
+
+        self.client.post_api(
+            &format!("{}/api/setpermissions", self.url),
+            &[
+                ("name", username),
+                ("type", relationship),
+                ("permissions", &ModPermission::join(permissions)),
+                ("api_type", "json"),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Remove `username` as a moderator.
+    pub fn remove_moderator(&self, username: &str) -> Result<()> {
+        self.unfriend(username, "moderator", Scope::ModOthers)
+    }
+
+    /// Accept an invite to moderate this subreddit, sent to the logged in user.
+    pub fn accept_moderator_invite(&self) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::ModSelf)?;
+
+        self.client.post_api(
+            &format!("{}/api/accept_moderator_invite", self.url),
+            &[("api_type", "json")],
+        )?;
+
+        Ok(())
+    }
+
+    /// What the moderators of this subreddit have done, newest first.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// use snew::moderation::{ModActionType, ModLogFilter};
+    /// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+    /// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+    ///
+    /// let filter = ModLogFilter {
+    ///     action: Some(ModActionType::BanUser),
+    ///     ..ModLogFilter::default()
+    /// };
+    ///
+    /// for action in reddit.subreddit("rust").mod_log(&filter)?.take(50) {
+    ///     let action = action?;
+    ///     println!("{} banned {:?}: {:?}", action.moderator, action.target_author, action.details);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mod_log(&self, filter: &ModLogFilter) -> Result<ModActionFeed> {
+        self.client.require_login()?;
+
+        Ok(ModActionFeed {
+            limit: 100,
+            url: self.mod_log_url(filter),
+            cached_actions: Vec::new(),
+            client: self.client.clone(),
+            cursor: Cursor::default(),
+        })
+    }
+
+    /// An endless stream of new moderator actions, oldest first. See [`Stream`].
+    pub fn stream_mod_log(&self, filter: &ModLogFilter) -> Result<Stream<ModAction>> {
+        self.client.require_login()?;
+
+        Ok(Stream::create(
+            self.mod_log_url(filter),
+            self.client.clone(),
+        ))
+    }
+
+    // The filters are part of the url, so streams can use them too.
+    fn mod_log_url(&self, filter: &ModLogFilter) -> String {
+        let mut queries = Vec::new();
+
+        if !filter.moderators.is_empty() {
+            queries.push(format!("mod={}", filter.moderators.join(",")));
+        }
+        if let Some(action) = &filter.action {
+            queries.push(format!("type={}", action.as_str()));
+        }
+
+        format!("{}/about/log?{}", self.url, queries.join("&"))
+    }
+
+    fn related_users(&self, path: &str) -> Result<RelatedUserFeed> {
+        self.client.require_login()?;
+
+        Ok(RelatedUserFeed {
+            limit: 100,
+            url: format!("{}/about/{}", self.url, path),
+            cached_users: Vec::new(),
+            client: self.client.clone(),
+            cursor: Cursor::default(),
+        })
+    }
+
+    // Reddit calls adding a relationship of any type 'friending'.
+    fn friend(
+        &self,
+        username: &str,
+        relationship: &str,
+        scope: Scope,
+        form: &[(&str, &str)],
+    ) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(scope)?;
+
+        let mut form = form.to_vec();
+        form.extend(&[
+            ("name", username),
+            ("type", relationship),
+            ("api_type", "json"),
+        ]);
+
+        self.client
+            .post_api(&format!("{}/api/friend", self.url), &form)?;
+
+        Ok(())
+    }
+
+    fn unfriend(&self, username: &str, relationship: &str, scope: Scope) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(scope)?;
+
+        self.client.post_api(
+            &format!("{}/api/unfriend", self.url),
+            &[
+                ("name", username),
+                ("type", relationship),
+                ("api_type", "json"),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// How to ban a user, see [`Subreddit::ban`]. The default is a permanent ban without a reason.
+#[derive(Debug, Clone, Default)]
+pub struct BanOptions {
+    /// In days, at most 999. None for a permanent ban.
+    pub duration: Option<u32>,
+    /// The reason, shown to moderators. At most 100 characters.
+    pub reason: Option<String>,
+    /// A note for the other moderators, at most 300 characters.
+    pub note: Option<String>,
+    /// Sent to the banned user, in markdown.
+    pub message: Option<String>,
+}
+
+/// A user with a relationship to a subreddit, e.g. a banned user or an approved contributor.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelatedUser {
+    pub name: String,
+    /// The fullname of the user, e.g. t2_abc123.
+    pub id: String,
+    /// When the relationship was created, in seconds since the UNIX epoch.
+    pub date: f64,
+    /// For bans, the reason.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// For bans, the days left until the ban ends. None if the ban is permanent.
+    #[serde(default)]
+    pub days_left: Option<u32>,
+}
+
+/// A moderator of a subreddit, see [`Subreddit::moderators`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Moderator {
+    pub name: String,
+    /// The fullname of the user, e.g. t2_abc123.
+    pub id: String,
+    /// When they became a moderator, in seconds since the UNIX epoch.
+    pub date: f64,
+    /// E.g. "all", or "posts" and "wiki".
+    #[serde(rename = "mod_permissions")]
+    pub permissions: Vec<String>,
+}
+
+/// What a moderator is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModPermission {
+    All,
+    /// Manage bans, mutes and approved users.
+    Access,
+    ChatConfig,
+    ChatOperator,
+    /// Manage settings, rules and the sidebar.
+    Config,
+    Flair,
+    Mail,
+    Posts,
+    Wiki,
+}
+
+impl ModPermission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Access => "access",
+            Self::ChatConfig => "chat_config",
+            Self::ChatOperator => "chat_operator",
+            Self::Config => "config",
+            Self::Flair => "flair",
+            Self::Mail => "mail",
+            Self::Posts => "posts",
+            Self::Wiki => "wiki",
+        }
+    }
+
+    // Reddit takes permissions as changes to none, e.g. "-all,+posts,+wiki".
+    pub(crate) fn join(permissions: &[ModPermission]) -> String {
+        if permissions.contains(&Self::All) {
+            return String::from("+all");
+        }
+
+        let mut joined = String::from("-all");
+
+        for permission in permissions {
+            joined.push_str(",+");
+            joined.push_str(permission.as_str());
+        }
+
+        joined
+    }
+}
+
+/// A set of users with a relationship to a subreddit, meant to be iterated over. See [`crate::things::PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct RelatedUserFeed {
+    /// The amount of users to request from the Reddit API at once, at most 100.
+    pub limit: i32,
+    url: String,
+    cached_users: Vec<RelatedUser>,
+    client: Arc<AuthenticatedClient>,
+    cursor: Cursor,
+}
+
+impl Iterator for RelatedUserFeed {
+    type Item = Result<RelatedUser>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_users.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::Read)?;
+
+            let children: Vec<RelatedUser> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &[])?;
+
+            self.cached_users.extend(children.into_iter().rev());
+
+            Ok(self.cached_users.pop())
+        })
+    }
+}
+
+/// Which moderator actions to list, see [`Subreddit::mod_log`]. The default is all of them.
+#[derive(Debug, Clone, Default)]
+pub struct ModLogFilter {
+    /// Only actions by these moderators.
+    pub moderators: Vec<String>,
+    /// Only actions of this type.
+    pub action: Option<ModActionType>,
+}
+
+/// An entry in the mod log, see [`Subreddit::mod_log`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModAction {
+    pub id: String,
+    pub action: ModActionType,
+    /// The moderator who did it.
+    #[serde(rename = "mod")]
+    pub moderator: String,
+    pub subreddit: String,
+    /// The fullname of the post, comment or user the action was done to.
+    #[serde(default)]
+    pub target_fullname: Option<String>,
+    #[serde(default)]
+    pub target_author: Option<String>,
+    #[serde(default)]
+    pub target_title: Option<String>,
+    #[serde(default)]
+    pub target_permalink: Option<String>,
+    /// E.g. the duration of a ban, or which setting was changed.
+    #[serde(default)]
+    pub details: Option<String>,
+    /// E.g. the reason for a ban.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// When it happened, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+}
+
+/// The kinds of moderator actions. Actions snew doesn't know about are [`ModActionType::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum ModActionType {
+    BanUser,
+    UnbanUser,
+    MuteUser,
+    UnmuteUser,
+    SpamLink,
+    RemoveLink,
+    ApproveLink,
+    SpamComment,
+    RemoveComment,
+    ApproveComment,
+    AddModerator,
+    RemoveModerator,
+    InviteModerator,
+    UninviteModerator,
+    AcceptModeratorInvite,
+    SetPermissions,
+    AddContributor,
+    RemoveContributor,
+    WikiContributor,
+    RemoveWikiContributor,
+    WikiBanned,
+    WikiUnbanned,
+    WikiRevise,
+    EditSettings,
+    EditFlair,
+    Distinguish,
+    MarkNsfw,
+    Spoiler,
+    Unspoiler,
+    Sticky,
+    Unsticky,
+    Lock,
+    Unlock,
+    SetSuggestedSort,
+    SetContestMode,
+    UnsetContestMode,
+    IgnoreReports,
+    UnignoreReports,
+    CreateRule,
+    EditRule,
+    DeleteRule,
+    Other(String),
+}
+
+impl ModActionType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BanUser => "banuser",
+            Self::UnbanUser => "unbanuser",
+            Self::MuteUser => "muteuser",
+            Self::UnmuteUser => "unmuteuser",
+            Self::SpamLink => "spamlink",
+            Self::RemoveLink => "removelink",
+            Self::ApproveLink => "approvelink",
+            Self::SpamComment => "spamcomment",
+            Self::RemoveComment => "removecomment",
+            Self::ApproveComment => "approvecomment",
+            Self::AddModerator => "addmoderator",
+            Self::RemoveModerator => "removemoderator",
+            Self::InviteModerator => "invitemoderator",
+            Self::UninviteModerator => "uninvitemoderator",
+            Self::AcceptModeratorInvite => "acceptmoderatorinvite",
+            Self::SetPermissions => "setpermissions",
+            Self::AddContributor => "addcontributor",
+            Self::RemoveContributor => "removecontributor",
+            Self::WikiContributor => "wikicontributor",
+            Self::RemoveWikiContributor => "removewikicontributor",
+            Self::WikiBanned => "wikibanned",
+            Self::WikiUnbanned => "wikiunbanned",
+            Self::WikiRevise => "wikirevise",
+            Self::EditSettings => "editsettings",
+            Self::EditFlair => "editflair",
+            Self::Distinguish => "distinguish",
+            Self::MarkNsfw => "marknsfw",
+            Self::Spoiler => "spoiler",
+            Self::Unspoiler => "unspoiler",
+            Self::Sticky => "sticky",
+            Self::Unsticky => "unsticky",
+            Self::Lock => "lock",
+            Self::Unlock => "unlock",
+            Self::SetSuggestedSort => "setsuggestedsort",
+            Self::SetContestMode => "setcontestmode",
+            Self::UnsetContestMode => "unsetcontestmode",
+            Self::IgnoreReports => "ignorereports",
+            Self::UnignoreReports => "unignorereports",
+            Self::CreateRule => "createrule",
+            Self::EditRule => "editrule",
+            Self::DeleteRule => "deleterule",
+            Self::Other(action) => action,
+        }
+    }
+}
+
+impl From<String> for ModActionType {
+    fn from(action: String) -> Self {
+        match action.as_str() {
+            "banuser" => Self::BanUser,
+            "unbanuser" => Self::UnbanUser,
+            "muteuser" => Self::MuteUser,
+            "unmuteuser" => Self::UnmuteUser,
+            "spamlink" => Self::SpamLink,
+            "removelink" => Self::RemoveLink,
+            "approvelink" => Self::ApproveLink,
+            "spamcomment" => Self::SpamComment,
+            "removecomment" => Self::RemoveComment,
+            "approvecomment" => Self::ApproveComment,
+            "addmoderator" => Self::AddModerator,
+            "removemoderator" => Self::RemoveModerator,
+            "invitemoderator" => Self::InviteModerator,
+            "uninvitemoderator" => Self::UninviteModerator,
+            "acceptmoderatorinvite" => Self::AcceptModeratorInvite,
+            "setpermissions" => Self::SetPermissions,
+            "addcontributor" => Self::AddContributor,
+            "removecontributor" => Self::RemoveContributor,
+            "wikicontributor" => Self::WikiContributor,
+            "removewikicontributor" => Self::RemoveWikiContributor,
+            "wikibanned" => Self::WikiBanned,
+            "wikiunbanned" => Self::WikiUnbanned,
+            "wikirevise" => Self::WikiRevise,
+            "editsettings" => Self::EditSettings,
+            "editflair" => Self::EditFlair,
+            "distinguish" => Self::Distinguish,
+            "marknsfw" => Self::MarkNsfw,
+            "spoiler" => Self::Spoiler,
+            "unspoiler" => Self::Unspoiler,
+            "sticky" => Self::Sticky,
+            "unsticky" => Self::Unsticky,
+            "lock" => Self::Lock,
+            "unlock" => Self::Unlock,
+            "setsuggestedsort" => Self::SetSuggestedSort,
+            "setcontestmode" => Self::SetContestMode,
+            "unsetcontestmode" => Self::UnsetContestMode,
+            "ignorereports" => Self::IgnoreReports,
+            "unignorereports" => Self::UnignoreReports,
+            "createrule" => Self::CreateRule,
+            "editrule" => Self::EditRule,
+            "deleterule" => Self::DeleteRule,
+            _ => Self::Other(action),
+        }
+    }
+}
+
+impl std::fmt::Display for ModActionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The mod log, meant to be iterated over. See [`crate::things::PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct ModActionFeed {
+    /// The amount of actions to request from the Reddit API at once, at most 500.
+    pub limit: i32,
+    url: String,
+    cached_actions: Vec<ModAction>,
+    client: Arc<AuthenticatedClient>,
+    cursor: Cursor,
+}
+
+impl Iterator for ModActionFeed {
+    type Item = Result<ModAction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_actions.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::ModLog)?;
+
+            let children: Vec<RawKind<ModAction>> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &[])?;
+
+            self.cached_actions
+                .extend(children.into_iter().rev().map(|raw| raw.data));
+
+            Ok(self.cached_actions.pop())
+        })
+    }
+}
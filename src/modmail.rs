@@ -0,0 +1,488 @@
+//! New modmail: conversations between the moderators of a subreddit and users, or among the moderators.
+use crate::{
+    auth::{AuthenticatedClient, Scope},
+    reddit::{Result, URL},
+    things::Transpose,
+};
+
+use serde::Deserialize;
+
+use std::{collections::HashMap, sync::Arc};
+
+/// A handle into the modmail of the subreddits the logged in user moderates. Use [`crate::reddit::Reddit::modmail()`] to get one.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// use snew::modmail::ConversationState;
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+/// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+///
+/// let modmail = reddit.modmail()?;
+///
+/// for conversation in modmail.conversations(ConversationState::New).take(10) {
+///     let conversation = modmail.conversation(&conversation?.id)?;
+///
+///     for message in &conversation.messages {
+///         println!("{}: {}", message.author.name, message.body_markdown);
+///     }
+///
+///     conversation.reply("Thanks, we're looking into it.", false, true)?;
+///     conversation.archive()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Modmail {
+    client: Arc<AuthenticatedClient>,
+}
+
+impl Modmail {
+    pub(crate) fn create(client: Arc<AuthenticatedClient>) -> Self {
+        Self { client }
+    }
+
+    /// The conversations in `state`, most recently updated first. Only the latest message of each conversation is included, use [`Self::conversation`] for the rest.
+    pub fn conversations(&self, state: ConversationState) -> ConversationFeed {
+        ConversationFeed {
+            limit: 100,
+            state,
+            subreddits: Vec::new(),
+            cached_conversations: Vec::new(),
+            client: self.client.clone(),
+            after: None,
+            done: false,
+        }
+    }
+
+    /// A conversation with all its messages and moderator actions. Reading it does not mark it as read.
+    pub fn conversation(&self, id: &str) -> Result<Conversation> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        let text = self
+            .client
+            .get(
+                &format!("{}/api/mod/conversations/{}", URL, id),
+                Some(&[("markRead", "false")]),
+            )?
+            .text()?;
+
+        Conversation::parse(&text, self.client.clone())
+    }
+
+    /// Start a conversation from `subreddit` with the user `to`. If `hide_author` is true, it is sent as the subreddit instead of the logged in moderator.
+    pub fn start_conversation(
+        &self,
+        subreddit: &str,
+        to: &str,
+        subject: &str,
+        body: &str,
+        hide_author: bool,
+    ) -> Result<Conversation> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        let text = self
+            .client
+            .post(
+                &format!("{}/api/mod/conversations", URL),
+                &[
+                    ("srName", subreddit),
+                    ("to", to),
+                    ("subject", subject),
+                    ("body", body),
+                    ("isAuthorHidden", &hide_author.to_string()),
+                ],
+            )?
+            .text()?;
+
+        Conversation::parse(&text, self.client.clone())
+    }
+
+    /// Mark the conversations with the given ids as read.
+    pub fn mark_read(&self, ids: &[&str]) -> Result<()> {
+        self.set_read(ids, "read")
+    }
+
+    /// Mark the conversations with the given ids as unread.
+    pub fn mark_unread(&self, ids: &[&str]) -> Result<()> {
+        self.set_read(ids, "unread")
+    }
+
+    fn set_read(&self, ids: &[&str], path: &str) -> Result<()> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        self.client.post(
+            &format!("{}/api/mod/conversations/{}", URL, path),
+            &[("conversationIds", ids.join(","))],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Which conversations to list, see [`Modmail::conversations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationState {
+    All,
+    New,
+    InProgress,
+    Archived,
+    Highlighted,
+    /// Conversations among the moderators only.
+    Mod,
+    /// Automatic notifications, e.g. about new moderators.
+    Notifications,
+    /// Ban appeals.
+    Appeals,
+    JoinRequests,
+}
+
+impl ConversationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::New => "new",
+            Self::InProgress => "inprogress",
+            Self::Archived => "archived",
+            Self::Highlighted => "highlighted",
+            Self::Mod => "mod",
+            Self::Notifications => "notifications",
+            Self::Appeals => "appeals",
+            Self::JoinRequests => "join_requests",
+        }
+    }
+}
+
+/// A modmail conversation.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    client: Arc<AuthenticatedClient>,
+    pub id: String,
+    pub subject: String,
+    /// The subreddit the conversation belongs to.
+    pub subreddit: String,
+    /// The user the moderators are talking with. None for conversations among the moderators.
+    pub participant: Option<ModmailAuthor>,
+    /// Only visible to moderators.
+    pub is_internal: bool,
+    pub is_highlighted: bool,
+    /// Sent automatically by Reddit.
+    pub is_auto: bool,
+    pub num_messages: u32,
+    /// In ISO 8601, e.g. 2021-07-01T12:00:00.000000+00:00.
+    pub last_updated: String,
+    /// Oldest first. When listing conversations, only the latest message is included.
+    pub messages: Vec<ModmailMessage>,
+    /// Oldest first, e.g. archiving or highlighting. Empty when listing conversations.
+    pub mod_actions: Vec<ModmailAction>,
+}
+
+impl Conversation {
+    /// Reply to this conversation. If `internal` is true, the reply is a note only the moderators can see.
+    /// If `hide_author` is true, it is sent as the subreddit instead of the logged in moderator.
+    /// Returns the conversation with the reply.
+    pub fn reply(&self, body: &str, internal: bool, hide_author: bool) -> Result<Conversation> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        let text = self
+            .client
+            .post(
+                &self.url(),
+                &[
+                    ("body", body),
+                    ("isInternal", &internal.to_string()),
+                    ("isAuthorHidden", &hide_author.to_string()),
+                ],
+            )?
+            .text()?;
+
+        Self::parse(&text, self.client.clone())
+    }
+
+    pub fn archive(&self) -> Result<()> {
+        self.action("archive")
+    }
+
+    pub fn unarchive(&self) -> Result<()> {
+        self.action("unarchive")
+    }
+
+    pub fn highlight(&self) -> Result<()> {
+        self.action("highlight")
+    }
+
+    pub fn unhighlight(&self) -> Result<()> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        self.client.delete(&format!("{}/highlight", self.url()))?;
+
+        Ok(())
+    }
+
+    /// Mute the participant, so they cannot message the moderators. `days` must be 3, 7 or 28.
+    pub fn mute_participant(&self, days: u32) -> Result<()> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        self.client.post(
+            &format!("{}/mute", self.url()),
+            &[("num_hours", (days * 24).to_string())],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn unmute_participant(&self) -> Result<()> {
+        self.action("unmute")
+    }
+
+    pub fn mark_read(&self) -> Result<()> {
+        Modmail::create(self.client.clone()).mark_read(&[&self.id])
+    }
+
+    pub fn mark_unread(&self) -> Result<()> {
+        Modmail::create(self.client.clone()).mark_unread(&[&self.id])
+    }
+
+    fn action(&self, path: &str) -> Result<()> {
+        self.client.require_scope(Scope::ModMail)?;
+
+        self.client.post(&format!("{}/{}", self.url(), path), &())?;
+
+        Ok(())
+    }
+
+    fn url(&self) -> String {
+        format!("{}/api/mod/conversations/{}", URL, self.id)
+    }
+
+    pub(crate) fn parse(text: &str, client: Arc<AuthenticatedClient>) -> Result<Self> {
+        let raw: RawConversationDetails = serde_json::from_str(text)?;
+
+        Ok(Self::from_raw(
+            raw.conversation,
+            &raw.messages,
+            &raw.mod_actions,
+            client,
+        ))
+    }
+
+    // A page of conversations, in the order Reddit sorted them. Pages don't include mod actions.
+    pub(crate) fn parse_page(text: &str, client: Arc<AuthenticatedClient>) -> Result<Vec<Self>> {
+        let RawConversations {
+            mut conversations,
+            conversation_ids,
+            messages,
+        } = serde_json::from_str(text)?;
+
+        let no_actions = HashMap::new();
+
+        Ok(conversation_ids
+            .iter()
+            .filter_map(|id| conversations.remove(id))
+            .map(|conversation| {
+                Self::from_raw(conversation, &messages, &no_actions, client.clone())
+            })
+            .collect())
+    }
+
+    // Conversations only refer to their messages and actions by id.
+    fn from_raw(
+        raw: RawConversation,
+        messages: &HashMap<String, ModmailMessage>,
+        mod_actions: &HashMap<String, ModmailAction>,
+        client: Arc<AuthenticatedClient>,
+    ) -> Self {
+        let objects = |key: &str| {
+            raw.obj_ids
+                .iter()
+                .filter(|object| object.key == key)
+                .map(|object| object.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            client,
+            messages: objects("messages")
+                .iter()
+                .filter_map(|id| messages.get(id).cloned())
+                .collect(),
+            mod_actions: objects("modActions")
+                .iter()
+                .filter_map(|id| mod_actions.get(id).cloned())
+                .collect(),
+            id: raw.id,
+            subject: raw.subject,
+            subreddit: raw.owner.display_name,
+            participant: raw
+                .participant
+                .filter(|participant| !participant.name.is_empty()),
+            is_internal: raw.is_internal,
+            is_highlighted: raw.is_highlighted,
+            is_auto: raw.is_auto,
+            num_messages: raw.num_messages,
+            last_updated: raw.last_updated,
+        }
+    }
+}
+
+/// A message in a modmail conversation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailMessage {
+    pub id: String,
+    pub author: ModmailAuthor,
+    /// In HTML.
+    pub body: String,
+    pub body_markdown: String,
+    /// A note only the moderators can see.
+    pub is_internal: bool,
+    /// In ISO 8601.
+    pub date: String,
+}
+
+/// Something a moderator did in a modmail conversation, e.g. archiving it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailAction {
+    pub id: String,
+    pub author: ModmailAuthor,
+    /// Reddit's numeric id of the kind of action.
+    pub action_type_id: u32,
+    /// In ISO 8601.
+    pub date: String,
+}
+
+/// The author of a modmail message, or the participant of a conversation.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ModmailAuthor {
+    pub name: String,
+    pub is_mod: bool,
+    pub is_admin: bool,
+    /// The original poster of the conversation.
+    pub is_op: bool,
+    pub is_participant: bool,
+    /// Sent as the subreddit.
+    pub is_hidden: bool,
+    pub is_deleted: bool,
+}
+
+/// A set of modmail conversations, meant to be iterated over. See [`crate::things::PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct ConversationFeed {
+    /// The amount of conversations to request from the Reddit API at once, at most 100.
+    pub limit: i32,
+    state: ConversationState,
+    subreddits: Vec<String>,
+    cached_conversations: Vec<Conversation>,
+    client: Arc<AuthenticatedClient>,
+    after: Option<String>,
+    done: bool,
+}
+
+impl ConversationFeed {
+    /// Only conversations in these subreddits. By default, all subreddits the logged in user moderates are included.
+    pub fn in_subreddits(mut self, subreddits: &[&str]) -> Self {
+        self.subreddits = subreddits.iter().map(|name| String::from(*name)).collect();
+        self
+    }
+}
+
+impl Iterator for ConversationFeed {
+    type Item = Result<Conversation>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_conversations
+            .pop()
+            .map(Ok)
+            .or_else_transpose(|| {
+                if self.done {
+                    return Ok(None);
+                }
+
+                self.client.require_scope(Scope::ModMail)?;
+
+                let mut queries = vec![
+                    ("limit", self.limit.to_string()),
+                    ("state", String::from(self.state.as_str())),
+                    ("sort", String::from("recent")),
+                ];
+
+                if let Some(after) = &self.after {
+                    queries.push(("after", after.clone()));
+                }
+                if !self.subreddits.is_empty() {
+                    queries.push(("entity", self.subreddits.join(",")));
+                }
+
+                let text = self
+                    .client
+                    .get(&format!("{}/api/mod/conversations", URL), Some(&queries))?
+                    .text()?;
+
+                let conversations = Conversation::parse_page(&text, self.client.clone())?;
+
+                // Pagination is by the id of the last conversation.
+                match conversations.last() {
+                    Some(last) => self.after = Some(last.id.clone()),
+                    None => return Ok(None),
+                }
+
+                if conversations.len() < self.limit as usize {
+                    self.done = true;
+                }
+
+                self.cached_conversations
+                    .extend(conversations.into_iter().rev());
+
+                Ok(self.cached_conversations.pop())
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConversations {
+    conversations: HashMap<String, RawConversation>,
+    conversation_ids: Vec<String>,
+    messages: HashMap<String, ModmailMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConversationDetails {
+    conversation: RawConversation,
+    #[serde(default)]
+    messages: HashMap<String, ModmailMessage>,
+    #[serde(default)]
+    mod_actions: HashMap<String, ModmailAction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConversation {
+    id: String,
+    subject: String,
+    owner: RawOwner,
+    #[serde(default)]
+    participant: Option<ModmailAuthor>,
+    is_internal: bool,
+    is_highlighted: bool,
+    is_auto: bool,
+    num_messages: u32,
+    last_updated: String,
+    obj_ids: Vec<RawObjId>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOwner {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawObjId {
+    id: String,
+    key: String,
+}
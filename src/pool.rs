@@ -0,0 +1,117 @@
+//! Share load between several accounts.
+use crate::{
+    auth::{Authenticator, IntoUserAgent},
+    reddit::{Reddit, Result},
+};
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// A pool of accounts, each with its own authenticator, token and rate limit budget.
+/// Use [`RedditPool::pick`] to get an account for each request, or [`RedditPool::pin`] to use a specific account for an action.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// use snew::{pool::RedditPool, auth::{ScriptAuthenticator, Credentials}};
+///
+/// let user_agent = "<Operating system>:snew:v0.1.0 (by u/<reddit username>)";
+///
+/// let mut pool = RedditPool::new();
+/// pool.add("helper1", ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "helper1", "password")), user_agent)?;
+/// pool.add("helper2", ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "helper2", "password")), user_agent)?;
+///
+/// // Whichever account has the most requests left.
+/// for post in pool.pick().unwrap().subreddit("rust").new().take(5) {
+///     // do something
+/// }
+///
+/// // Always the same account.
+/// println!("{:?}", pool.pin("helper1").unwrap().me()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RedditPool {
+    accounts: Vec<(String, Reddit)>,
+    // Where to start looking, so accounts with the same budget take turns.
+    next: Arc<AtomicUsize>,
+}
+
+impl RedditPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log in with `authenticator`, and add the account to the pool as `name`.
+    pub fn add<T: Authenticator + 'static>(
+        &mut self,
+        name: impl ToString,
+        authenticator: T,
+        user_agent: impl IntoUserAgent,
+    ) -> Result<()> {
+        self.add_reddit(name, Reddit::new(authenticator, user_agent)?);
+
+        Ok(())
+    }
+
+    /// Add an account you are already logged in with.
+    pub fn add_reddit(&mut self, name: impl ToString, reddit: Reddit) {
+        self.accounts.push((name.to_string(), reddit));
+    }
+
+    /// Remove the account named `name`, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<Reddit> {
+        let index = self.accounts.iter().position(|(n, _)| n == name)?;
+
+        Some(self.accounts.remove(index).1)
+    }
+
+    /// Pick the account with the most requests left before being rate limited. Accounts with the same budget take turns.
+    /// Returns None if the pool is empty.
+    pub fn pick(&self) -> Option<Reddit> {
+        if self.accounts.is_empty() {
+            return None;
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % self.accounts.len();
+
+        // Unknown budgets haven't been used yet, or have been reset, so prefer them.
+        let budget = |reddit: &Reddit| reddit.rate_limit().remaining_now().unwrap_or(f64::MAX);
+
+        let mut best: Option<&Reddit> = None;
+
+        for i in 0..self.accounts.len() {
+            let reddit = &self.accounts[(start + i) % self.accounts.len()].1;
+
+            best = match best {
+                Some(best) if budget(best) >= budget(reddit) => Some(best),
+                _ => Some(reddit),
+            };
+        }
+
+        best.cloned()
+    }
+
+    /// The account named `name`, to use the same account for an entire action.
+    pub fn pin(&self, name: &str) -> Option<Reddit> {
+        self.accounts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, reddit)| reddit.clone())
+    }
+
+    /// The names of the accounts in the pool.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.accounts.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
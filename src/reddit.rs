@@ -1,9 +1,17 @@
 //! Reddit API.
-use crate::auth::{AuthenticatedClient, Authenticator, UserAuthenticator};
-use crate::things::*;
+use crate::auth::{AuthenticatedClient, Authenticator, IntoUserAgent, RateLimit, Scope};
+#[cfg(feature = "code_flow")]
+use crate::auth::{TokenDuration, UserAuthenticator};
+use crate::inbox::Inbox;
+use crate::modmail::Modmail;
+use crate::things::{
+    raw::{generic_kind::RawKind, listing::RawListing},
+    *,
+};
 
 use std::sync::{Arc, PoisonError};
-use std::time::{Duration, Instant};
+#[cfg(feature = "code_flow")]
+use std::time::Duration;
 
 use thiserror::Error;
 
@@ -43,7 +51,11 @@ pub struct Reddit {
 // The API calls.
 impl Reddit {
     /// Creates a new API connection, using the given authenticator.
-    pub fn new<T: Authenticator + 'static>(authenticator: T, user_agent: &str) -> Result<Self> {
+    /// The user agent can be a string, or a [`crate::auth::UserAgent`] in the format Reddit recommends.
+    pub fn new<T: Authenticator + 'static>(
+        authenticator: T,
+        user_agent: impl IntoUserAgent,
+    ) -> Result<Self> {
         let client = AuthenticatedClient::new(authenticator, user_agent)?;
 
         Ok(Self {
@@ -55,9 +67,16 @@ impl Reddit {
         self.inner.set_authenticator(authenticator);
     }
 
+    /// The rate limit budget of the current authenticator, as of the last request.
+    pub fn rate_limit(&self) -> RateLimit {
+        self.inner.rate_limit()
+    }
+
     /// Get information about the user, useful for debugging.
     pub fn me(&self) -> Result<Me> {
         if self.inner.authenticator.read().unwrap().is_logged_in() {
+            self.inner.require_scope(Scope::Identity)?;
+
             Ok(serde_json::from_str(
                 &self
                     .inner
@@ -69,6 +88,193 @@ impl Reddit {
         }
     }
 
+    /// The inbox of the logged in user, with private messages, comment replies and username mentions.
+    /// See [`Inbox`] for an example.
+    pub fn inbox(&self) -> Result<Inbox> {
+        self.inner.require_login()?;
+
+        Ok(Inbox::create(self.inner.clone()))
+    }
+
+    /// The modmail of the subreddits the logged in user moderates.
+    /// See [`Modmail`] for an example.
+    pub fn modmail(&self) -> Result<Modmail> {
+        self.inner.require_login()?;
+
+        Ok(Modmail::create(self.inner.clone()))
+    }
+
+    /// The subreddits the logged in user is subscribed to.
+    pub fn subscribed(&self) -> Result<SubredditFeed> {
+        self.my_subreddits("subscriber")
+    }
+
+    /// The subreddits the logged in user is an approved contributor in.
+    pub fn contributor_subreddits(&self) -> Result<SubredditFeed> {
+        self.my_subreddits("contributor")
+    }
+
+    /// The subreddits the logged in user moderates.
+    pub fn moderated_subreddits(&self) -> Result<SubredditFeed> {
+        self.my_subreddits("moderator")
+    }
+
+    /// Subreddits, the most popular first.
+    pub fn popular_subreddits(&self) -> SubredditFeed {
+        SubredditFeed::create(format!("{}/subreddits/popular", URL), self.inner.clone())
+    }
+
+    /// Subreddits, the newest first.
+    pub fn new_subreddits(&self) -> SubredditFeed {
+        SubredditFeed::create(format!("{}/subreddits/new", URL), self.inner.clone())
+    }
+
+    /// The subreddits new users are subscribed to by default.
+    pub fn default_subreddits(&self) -> SubredditFeed {
+        SubredditFeed::create(format!("{}/subreddits/default", URL), self.inner.clone())
+    }
+
+    /// Search for subreddits by name and description.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::ApplicationAuthenticator};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// for subreddit in reddit.search_subreddits("programming").take(10) {
+    ///     let subreddit = subreddit?;
+    ///     println!("{}: {:?} subscribers", subreddit.name, subreddit.about()?.subscribers);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_subreddits(&self, query: &str) -> SubredditFeed {
+        SubredditFeed::create(format!("{}/subreddits/search", URL), self.inner.clone())
+            .with_queries(&[("q", query)])
+    }
+
+    /// Subreddits with names starting with `query`, for e.g. a subreddit picker. Returns at most 10 subreddits.
+    pub fn autocomplete_subreddits(
+        &self,
+        query: &str,
+        include_nsfw: bool,
+    ) -> Result<Vec<Subreddit>> {
+        self.inner.require_scope(Scope::Read)?;
+
+        let text = self
+            .inner
+            .get(
+                &format!("{}/api/subreddit_autocomplete_v2", URL),
+                Some(&[
+                    ("query", query),
+                    ("include_over_18", &include_nsfw.to_string()),
+                    ("include_profiles", "false"),
+                    ("limit", "10"),
+                ]),
+            )?
+            .text()?;
+
+        let listing: RawListing<RawKind<SubredditAbout>> = serde_json::from_str(&text)?;
+
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .map(|raw| {
+                let mut subreddit = Subreddit::create(&raw.data.display_name, self.inner.clone());
+                subreddit.about = Some(raw.data);
+                subreddit
+            })
+            .collect())
+    }
+
+    /// The multireddits of the logged in user. See [`Multireddit`] for an example.
+    pub fn my_multireddits(&self) -> Result<Vec<Multireddit>> {
+        self.inner.require_login()?;
+
+        self.multireddits(&format!("{}/api/multi/mine", URL))
+    }
+
+    /// The public multireddits of `username`.
+    pub fn user_multireddits(&self, username: &str) -> Result<Vec<Multireddit>> {
+        self.multireddits(&format!("{}/api/multi/user/{}", URL, username))
+    }
+
+    /// The multireddit `name` of `username`.
+    pub fn multireddit(&self, username: &str, name: &str) -> Result<Multireddit> {
+        self.inner.require_scope(Scope::Read)?;
+
+        let text = self
+            .inner
+            .get(
+                &format!("{}/api/multi/user/{}/m/{}", URL, username, name),
+                None::<&()>,
+            )?
+            .text()?;
+
+        Multireddit::parse(&text, self.inner.clone())
+    }
+
+    /// Create a multireddit for the logged in user. `name` is used in the path, e.g. /user/spez/m/name, and may only contain letters, numbers and underscores.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// use snew::things::MultiredditOptions;
+    /// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+    /// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+    ///
+    /// let multireddit = reddit.create_multireddit(
+    ///     "languages",
+    ///     &MultiredditOptions::new("Languages", &["rust", "golang", "python"]),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_multireddit(
+        &self,
+        name: &str,
+        options: &MultiredditOptions,
+    ) -> Result<Multireddit> {
+        self.inner.require_login()?;
+        self.inner.require_scope(Scope::Subscribe)?;
+
+        let text = self
+            .inner
+            .post(
+                &format!(
+                    "{}/api/multi/user/{}/m/{}",
+                    URL,
+                    crate::things::username(&self.inner)?,
+                    name
+                ),
+                &[("model", options.model())],
+            )?
+            .text()?;
+
+        Multireddit::parse(&text, self.inner.clone())
+    }
+
+    fn multireddits(&self, url: &str) -> Result<Vec<Multireddit>> {
+        self.inner.require_scope(Scope::Read)?;
+
+        let text = self.inner.get(url, None::<&()>)?.text()?;
+
+        Multireddit::parse_all(&text, self.inner.clone())
+    }
+
+    fn my_subreddits(&self, path: &str) -> Result<SubredditFeed> {
+        self.inner.require_login()?;
+        self.inner.require_scope(Scope::MySubreddits)?;
+
+        Ok(SubredditFeed::create(
+            format!("{}/subreddits/mine/{}", URL, path),
+            self.inner.clone(),
+        ))
+    }
+
     /// Create a handle into a specific subreddit.
     /// # Usage
     /// ```no_run
@@ -109,13 +315,58 @@ impl Reddit {
         Subreddit::create(name, self.inner.clone())
     }
 
-    /// Posts from the frontpage.
+    /// Posts from the frontpage. For a user this is their subscriptions, otherwise the default subreddits.
     pub fn frontpage(&self) -> Subreddit {
-        Subreddit {
-            name: String::from("frontpage"),
-            url: URL.to_string(),
-            client: self.inner.clone(),
+        Subreddit::frontpage(self.inner.clone())
+    }
+
+    /// r/all, posts from all subreddits.
+    pub fn all(&self) -> Subreddit {
+        self.subreddit("all")
+    }
+
+    /// r/all, without posts from the `excluded` subreddits.
+    pub fn all_excluding(&self, excluded: &[&str]) -> Subreddit {
+        let mut name = String::from("all");
+
+        for subreddit in excluded {
+            name.push('-');
+            name.push_str(subreddit);
         }
+
+        self.subreddit(&name)
+    }
+
+    /// r/popular, posts from the most popular subreddits.
+    pub fn popular(&self) -> Subreddit {
+        self.subreddit("popular")
+    }
+
+    /// r/popular for a region. `geo_filter` is a country code like "US" or "DE", a US state like "US_CA", or "GLOBAL".
+    pub fn popular_in(&self, geo_filter: &str) -> Subreddit {
+        let mut popular = self.popular();
+        popular.geo_filter = Some(String::from(geo_filter));
+        popular
+    }
+
+    /// Several subreddits combined into one, with their posts ranked together.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::ApplicationAuthenticator};
+    /// # let reddit = Reddit::new(
+    /// #    ApplicationAuthenticator::new("client_id"),
+    /// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+    /// #    ).unwrap();
+    /// for post in reddit.combined(&["rust", "golang", "python"]).top().take(10) {
+    ///     let post = post?;
+    ///     println!("r/{}: {}", post.subreddit, post.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn combined(&self, subreddits: &[&str]) -> Subreddit {
+        self.subreddit(&subreddits.join("+"))
     }
 
     // /// Submit a text post.
@@ -137,71 +388,164 @@ impl Reddit {
             .expect("Poisoned mutex")
             .refresh_token()
     }
+
+    /// Log out, revoking the tokens of the current authenticator so they can no longer be used, e.g. for a "sign out" button.
+    /// If the authenticator has a refresh token, as returned by [`Reddit::refresh_token`], it is revoked too. A [`crate::auth::TokenStore`] attached to it is cleared.
+    ///
+    /// Only a [`crate::auth::UserAuthenticator`] stays logged out. [`crate::auth::ScriptAuthenticator`], [`crate::auth::ApplicationAuthenticator`] and
+    /// [`crate::auth::ClientCredentialsAuthenticator`] still have their credentials, so the next request simply logs in again.
+    pub fn logout(&self) -> Result<()> {
+        self.inner.authenticator.read()?.revoke(&self.inner.client)
+    }
+}
+
+/// How the authorization URL is handed to the user in [`Reddit::perform_code_flow`].
+#[cfg(feature = "code_flow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "code_flow")))]
+#[derive(Debug, Clone, Copy)]
+pub enum OpenUrl {
+    /// Open the URL in the users browser, using the ```opener``` crate.
+    Browser,
+    /// Print the URL to stdout, for headless machines.
+    Print,
+    /// Hand the URL to your own function, e.g. to show it in your UI.
+    Custom(fn(&str)),
+}
+
+/// Options for [`Reddit::perform_code_flow`]. Create them with [`CodeFlowOptions::new`], then change the fields you need.
+#[cfg(feature = "code_flow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "code_flow")))]
+#[derive(Debug, Clone)]
+pub struct CodeFlowOptions {
+    pub client_id: String,
+    /// The scopes to request. Check which ones the user actually granted with [`crate::auth::Token::scopes()`].
+    pub scopes: Vec<Scope>,
+    /// Must match the redirect URI of your application exactly. The local web server listens on the host and port of this URI.
+    /// Defaults to `http://localhost:8080`.
+    pub redirect_uri: String,
+    /// Defaults to [`TokenDuration::Permanent`].
+    pub duration: TokenDuration,
+    /// The text shown in the browser once the user is done.
+    pub success_response: String,
+    /// How long to wait for the user to complete their end of the flow. Waits forever if None, the default.
+    pub timeout: Option<Duration>,
+    /// Defaults to [`OpenUrl::Browser`].
+    pub open_url: OpenUrl,
+    /// Sent when trading the code for a token. Use the same one as for the [`Reddit`] the authenticator is meant for.
+    pub user_agent: crate::auth::UserAgent,
 }
 
 #[cfg(feature = "code_flow")]
+impl CodeFlowOptions {
+    pub fn new(
+        client_id: impl ToString,
+        scopes: &[Scope],
+        user_agent: impl IntoUserAgent,
+    ) -> Result<Self> {
+        Ok(Self {
+            client_id: client_id.to_string(),
+            scopes: scopes.to_vec(),
+            redirect_uri: String::from("http://localhost:8080"),
+            duration: TokenDuration::Permanent,
+            success_response: String::from(
+                "Done! You can close this window and return to the app.",
+            ),
+            timeout: None,
+            open_url: OpenUrl::Browser,
+            user_agent: user_agent.into_user_agent()?,
+        })
+    }
+}
+
+#[cfg(feature = "code_flow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "code_flow")))]
 impl Reddit {
-    /// A function that, from start to finish, performs the full OAuth2 code flow described in https://github.com/reddit-archive/reddit/wiki/OAuth2 and returns an Authenticator with a valid refresh token.
-    /// You can retrieve the token for serialization later with [`Authenticator::get_token()`].
+    /// A function that, from start to finish, performs the full OAuth2 code flow described in https://github.com/reddit-archive/reddit/wiki/OAuth2 and returns an Authenticator.
+    /// You can retrieve the token for serialization later with [`Authenticator::token()`], or persist it with [`UserAuthenticator::with_store()`].
     ///
     /// You will need a application registered following the instructions in [`Reddit`], noting:
     /// * Choose a _installed app_
-    /// * You MUST set the redirect URI to 'http://localhost:8080'.
+    /// * You MUST set the redirect URI to the one in [`CodeFlowOptions::redirect_uri`], 'http://localhost:8080' by default.
     ///
     /// In full, this function will:
-    /// * Spawn a web server on localhost, listening on port 8080.
-    /// * Use the ```opener``` crate to open a URL in the users browser.
+    /// * Spawn a web server listening on the host and port of the redirect URI.
+    /// * Give the user a URL to open, as configured by [`CodeFlowOptions::open_url`].
     /// * There, the user can accept that you would like to use Reddit on their behalf.
     /// * If they do, reddit makes a request to your local webserver, and gives us back a code.
-    /// * We trade that code in for a refresh token.
+    /// * We trade that code in for a token, which includes a refresh token if the duration is [`TokenDuration::Permanent`].
+    ///
+    /// If you would rather do this work yourself, e.g. in a web application, see [`crate::auth::CodeFlow`].
+    /// # Usage
+    /// ```no_run
+    /// use snew::{reddit::{Reddit, CodeFlowOptions, OpenUrl}, auth::Scope};
+    /// use std::time::Duration;
     ///
-    /// If you would rather do this work yourself, just get the refresh_token, and pass it to [`UserAuthenticator::new()`].
+    /// let mut options = CodeFlowOptions::new(
+    ///     "client_id",
+    ///     &[Scope::Identity, Scope::Read],
+    ///     "<Operating system>:snew:v0.1.0 (by u/<reddit username>)",
+    /// ).unwrap();
+    /// options.open_url = OpenUrl::Print;
+    /// options.timeout = Some(Duration::from_secs(180));
+    ///
+    /// let user_auth = Reddit::perform_code_flow(options).unwrap();
+    /// ```
     pub fn perform_code_flow(
-        client_id: impl std::fmt::Display,
-        success_response: &'static str,
-        timeout: Option<Duration>,
-    ) -> std::result::Result<UserAuthenticator, Box<dyn std::error::Error + Send + Sync>> {
+        options: CodeFlowOptions,
+    ) -> std::result::Result<UserAuthenticator, CodeFlowError> {
         use rand::Rng;
-        use reqwest::blocking::Client;
+        use reqwest::Url;
         use rouille::{Response as RouilleResponse, Server};
-        use std::sync::RwLock;
-
-        use crate::auth::parse_response;
+        use std::sync::{mpsc, Mutex};
 
-        let initial = Instant::now();
-        // Somewhat jank structure that holds either (state, code) or an error.
-        let result: Arc<RwLock<Option<std::result::Result<(String, String), String>>>> =
-            Arc::new(RwLock::new(None));
-        let copy = result.clone();
+        use crate::auth::CodeFlow;
 
-        // Spawn the server
-        let server = Server::new("localhost:8080", move |request| {
-            if let Some(error) = request.get_param("error") {
-                let response = format!("Something went wrong: {}", error);
-                *copy.write().unwrap() = Some(Err(error));
+        let invalid_redirect = || CodeFlowError::InvalidRedirectUri(options.redirect_uri.clone());
 
-                return RouilleResponse::text(response);
-            }
+        let redirect = Url::parse(&options.redirect_uri).map_err(|_| invalid_redirect())?;
+        let address = match (redirect.host_str(), redirect.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            _ => return Err(invalid_redirect()),
+        };
+        let path = redirect.path().to_string();
 
-            let state = request.get_param("state");
-            let code = request.get_param("code");
+        // The server sends either (state, code) or an error through this.
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let success_response = options.success_response.clone();
 
-            if let Some(state) = state {
-                if let Some(code) = code {
-                    *copy.write().unwrap() = Some(Ok((state, code)));
-                    return RouilleResponse::text(success_response);
-                }
+        // Spawn the server
+        let server = Server::new(address, move |request| {
+            // E.g. the browser asking for a favicon.
+            if request.url() != path {
+                return RouilleResponse::empty_404();
             }
 
-            let error_msg = format!("Missing state or code parameter. This is a bug from Reddit. Try again. Parameters reddit returned: {}", request.raw_query_string());
-            let response = RouilleResponse::text(&error_msg);
+            let result = if let Some(error) = request.get_param("error") {
+                Err(CodeFlowError::AuthorizationError(error))
+            } else if let (Some(state), Some(code)) =
+                (request.get_param("state"), request.get_param("code"))
+            {
+                Ok((state, code))
+            } else {
+                Err(CodeFlowError::MissingParameters(
+                    request.raw_query_string().to_string(),
+                ))
+            };
+
+            let response = match &result {
+                Ok(_) => RouilleResponse::text(success_response.clone()),
+                Err(error) => RouilleResponse::text(format!("Something went wrong: {}", error)),
+            };
 
-            *copy.write().unwrap() = Some(Err(error_msg));
+            // Nobody is listening anymore if we already got a result or timed out, so ignore errors.
+            let _ = sender.lock().unwrap().send(result);
 
             response
-        })?;
+        })
+        .map_err(CodeFlowError::ServerError)?;
 
-        let (_, sender) = server.stoppable();
+        let (_, stop) = server.stoppable();
 
         let state: String = rand::thread_rng()
             .sample_iter(&rand::distributions::Alphanumeric)
@@ -209,54 +553,41 @@ impl Reddit {
             .map(char::from)
             .collect();
 
-        let url = format!("https://www.reddit.com/api/v1/authorize?client_id={}&response_type=code\
-                                    &state={}&redirect_uri=http://localhost:8080&duration=permanent&scope=*", client_id, state);
+        let flow = CodeFlow::new(&options.client_id, &options.redirect_uri);
+        let url = flow.authorization_url(&state, &options.scopes, options.duration);
 
-        // Open the url
-        opener::open_browser(url)?;
-
-        // Spin while waiting for request. Could be more efficient, send an issue if this is actually causing a problem for you, and I will fix it.
-        while result.read().map_err(Into::<Error>::into)?.is_none() {
-            if let Some(timeout) = timeout {
-                if initial.elapsed() >= timeout {
-                    break;
-                }
+        let opened = match options.open_url {
+            OpenUrl::Browser => opener::open_browser(&url).map_err(Into::into),
+            OpenUrl::Print => {
+                println!("Open this URL in your browser to continue: {}", url);
+                Ok(())
             }
-        }
-
-        sender.send(())?;
+            OpenUrl::Custom(open) => {
+                open(&url);
+                Ok(())
+            }
+        };
 
-        // Must be some by this point
-        let result = result
-            .write()
-            .map_err(Into::<Error>::into)?
-            .take()
-            .unwrap()?;
+        // Block until the server receives a request, or we time out.
+        let result = opened.and_then(|_| match options.timeout {
+            Some(timeout) => receiver
+                .recv_timeout(timeout)
+                .map_err(|_| CodeFlowError::TimedOut),
+            None => receiver.recv().map_err(|_| CodeFlowError::TimedOut),
+        });
 
-        // Verify state
-        if state == result.0 {
-            let client = Client::new();
-
-            // Finally, get the refresh token.
-            let response = client
-                .post("https://www.reddit.com/api/v1/access_token")
-                .body(format!(
-                    "grant_type=authorization_code&code={}&redirect_uri={}",
-                    result.1, "http://localhost:8080"
-                ))
-                .basic_auth(&client_id, None::<String>)
-                .send()?;
+        // The server may already be gone, that's fine.
+        let _ = stop.send(());
 
-            let mut token = parse_response(response)?;
+        let (received_state, code) = result??;
 
-            Ok(UserAuthenticator::new_complete(
-                token.refresh_token.take().unwrap(),
-                client_id,
-                token.into(),
-            ))
-        } else {
-            Err(Box::new(CodeFlowError::StateDidNotMatch(state, result.0)))
+        // Verify state
+        if state != received_state {
+            return Err(CodeFlowError::StateDidNotMatch(state, received_state));
         }
+
+        // Finally, get the token.
+        Ok(flow.exchange_code(&code, options.user_agent)?)
     }
 }
 
@@ -292,6 +623,44 @@ pub enum Error {
     /// No content that snew knows how to handle.
     #[error("No parseable content found")]
     NoReadableContent,
+
+    /// Reddit refused the request even with a fresh token, e.g. because the logged in user does not moderate the subreddit.
+    #[error(
+        "The logged in user is not allowed to do this. Are they a moderator of the subreddit?"
+    )]
+    PermissionError,
+
+    /// The token was not granted the scope needed for this action. Request it when performing the code flow.
+    #[error("The current token was not granted the '{0}' scope, which this action requires.")]
+    MissingScope(Scope),
+
+    /// Reddit returned a scope snew does not know about.
+    #[error("Unknown scope: {0}")]
+    UnknownScope(String),
+
+    /// A required setting is missing from the [`crate::config::Config`], or the config file is malformed.
+    #[error("Invalid config.\nReason:\t{0}")]
+    ConfigError(String),
+
+    /// The user agent is malformed, see [`crate::auth::UserAgent`].
+    #[error("Invalid user agent.\nReason:\t{0}")]
+    InvalidUserAgent(String),
+
+    /// Not a known kind of thing, see [`crate::things::Kind`].
+    #[error("Unknown kind of thing: {0}")]
+    KindParseError(String),
+
+    /// The flair can't be set this way, see [`crate::flair::Flair`].
+    #[error("Invalid flair.\nReason:\t{0}")]
+    InvalidFlair(String),
+
+    /// Reddit understood the request, but refused it, e.g. because the recipient of a message doesn't exist.
+    #[error("Reddit returned an error.\nReason:\t{0}")]
+    ApiError(String),
+
+    /// Reading or writing a file failed, e.g. in a [`crate::auth::FileTokenStore`].
+    #[error("IO error.\nCaused by:\t{0}")]
+    IoError(#[from] std::io::Error),
 }
 
 impl<T> From<PoisonError<T>> for Error {
@@ -300,13 +669,28 @@ impl<T> From<PoisonError<T>> for Error {
     }
 }
 
+/// Errors that can occur in [`Reddit::perform_code_flow`].
 #[cfg(feature = "code_flow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "code_flow")))]
 #[derive(Error, Debug)]
 pub enum CodeFlowError {
     #[error("Received state did not match original state. Original:\t{0}\tReceived:\t{1}")]
     StateDidNotMatch(String, String),
     #[error("Missing state or code parameters. Received:\t{0}")]
     MissingParameters(String),
+    /// Reddit redirected with an error, e.g. 'access_denied' if the user declined.
+    #[error("Reddit returned an error:\t{0}")]
+    AuthorizationError(String),
+    /// The user did not complete the flow within the timeout.
+    #[error("Timed out waiting for the user to complete the code flow")]
+    TimedOut,
+    #[error("Invalid redirect URI, expected something like http://localhost:8080. Received:\t{0}")]
+    InvalidRedirectUri(String),
+    /// The local web server could not be started, e.g. because the port is taken.
+    #[error("Failed to start the local web server.\nCaused by:\t{0}")]
+    ServerError(Box<dyn std::error::Error + Send + Sync>),
+    #[error("Failed to open the URL in the browser.\nCaused by:\t{0}")]
+    OpenUrlError(#[from] opener::OpenError),
     #[error("Other error:\t{0}")]
     RedditError(#[from] Error),
 }
@@ -0,0 +1,217 @@
+//! Endless streams of new posts, comments and moderator actions, see [`crate::things::Subreddit::stream_submissions`].
+use crate::{
+    auth::{AuthenticatedClient, Scope},
+    moderation::ModAction,
+    reddit::Result,
+    things::{
+        raw::{
+            comment::RawCommentData, generic_kind::RawKind, listing::RawListing, post::RawPostData,
+        },
+        Comment, Post,
+    },
+};
+
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+// The shortest wait between polls, even when there is always something new.
+const MIN_DELAY: Duration = Duration::from_secs(1);
+
+/// An endless stream of new items, oldest first. Polls Reddit, and only yields items it has not seen before.
+/// It polls at most once a second, and when there is nothing new, it waits longer and longer between polls, up to [`Self::max_delay`].
+///
+/// Errors, e.g. from the HTTP request, are yielded, but the stream keeps going if you call next() again.
+/// Using `?` on the items ends your loop on the first error, which may be transient.
+/// # Usage
+/// ```no_run
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new(
+/// #    "client_id",
+/// #   "client_secret",
+/// #   "username",
+/// #   "password",
+/// # ));
+/// # let reddit = Reddit::new(
+/// #    script_auth,
+/// #    "<Operating system>:snew:v0.1.0 (by u/<reddit username>)"
+/// #    ).unwrap();
+/// for post in reddit.subreddit("rust").stream_submissions() {
+///     match post {
+///         Ok(post) => println!("New post: {}", post.title),
+///         Err(error) => eprintln!("Trying again: {}", error),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Stream<T> {
+    /// Skip the items that already exist when the stream is created, only yielding ones created after. Defaults to false.
+    pub skip_existing: bool,
+    /// The longest to wait between polls when nothing new appears. Defaults to 16 seconds.
+    pub max_delay: Duration,
+    /// How many ids to remember to recognize items that were already yielded. Defaults to 300.
+    pub seen_capacity: usize,
+    url: String,
+    client: Arc<AuthenticatedClient>,
+    // Items to yield, newest first so pop() gives the oldest.
+    cached: Vec<T>,
+    seen: HashSet<String>,
+    // The order ids were seen in, to forget the oldest first.
+    seen_order: VecDeque<String>,
+    delay: Duration,
+    first_poll: bool,
+}
+
+impl<T: Streamable> Stream<T> {
+    pub(crate) fn create(url: String, client: Arc<AuthenticatedClient>) -> Self {
+        Self {
+            skip_existing: false,
+            max_delay: Duration::from_secs(16),
+            seen_capacity: 300,
+            url,
+            client,
+            cached: Vec::new(),
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            delay: Duration::from_secs(0),
+            first_poll: true,
+        }
+    }
+
+    // Fetch the newest items, keeping the ones not seen before.
+    fn poll(&mut self) -> Result<()> {
+        let items = T::fetch(&self.client, &self.url)?;
+
+        // Oldest first, so the oldest ids are also forgotten first.
+        for item in items.into_iter().rev() {
+            let id = item.id().to_string();
+
+            if self.seen.insert(id.clone()) {
+                self.seen_order.push_back(id);
+                self.cached.push(item);
+            }
+        }
+
+        self.cached.reverse();
+
+        while self.seen_order.len() > self.seen_capacity {
+            if let Some(id) = self.seen_order.pop_front() {
+                self.seen.remove(&id);
+            }
+        }
+
+        if self.first_poll {
+            self.first_poll = false;
+
+            if self.skip_existing {
+                self.cached.clear();
+            }
+        }
+
+        Ok(())
+    }
+
+    // Wait a bit longer every time.
+    fn back_off(&mut self) {
+        self.delay = (self.delay * 2).max(MIN_DELAY).min(self.max_delay);
+    }
+}
+
+impl<T: Streamable> Iterator for Stream<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.cached.pop() {
+                return Some(Ok(item));
+            }
+
+            thread::sleep(self.delay);
+
+            match self.poll() {
+                Ok(()) if self.cached.is_empty() => self.back_off(),
+                // Still wait a little, so a busy subreddit doesn't use up the rate limit.
+                Ok(()) => self.delay = MIN_DELAY.min(self.max_delay),
+                Err(error) => {
+                    self.back_off();
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+/// Something that can be streamed, see [`Stream`].
+pub trait Streamable: Sized {
+    #[doc(hidden)]
+    // The newest items at `url`, newest first.
+    fn fetch(client: &Arc<AuthenticatedClient>, url: &str) -> Result<Vec<Self>>;
+    #[doc(hidden)]
+    fn id(&self) -> &str;
+}
+
+impl Streamable for Post {
+    fn fetch(client: &Arc<AuthenticatedClient>, url: &str) -> Result<Vec<Self>> {
+        client.require_scope(Scope::Read)?;
+
+        let text = client.get(url, Some(&[("limit", "100")]))?.text()?;
+
+        let listing: RawListing<RawKind<RawPostData>> = serde_json::from_str(&text)?;
+
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .map(|raw| (raw, client.clone()).into())
+            .collect())
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Streamable for Comment {
+    fn fetch(client: &Arc<AuthenticatedClient>, url: &str) -> Result<Vec<Self>> {
+        client.require_scope(Scope::Read)?;
+
+        let text = client.get(url, Some(&[("limit", "100")]))?.text()?;
+
+        let listing: RawListing<RawKind<RawCommentData>> = serde_json::from_str(&text)?;
+
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .map(|raw| (raw, client.clone()).into())
+            .collect())
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Streamable for ModAction {
+    fn fetch(client: &Arc<AuthenticatedClient>, url: &str) -> Result<Vec<Self>> {
+        client.require_scope(Scope::ModLog)?;
+
+        let text = client.get(url, Some(&[("limit", "100")]))?.text()?;
+
+        let listing: RawListing<RawKind<ModAction>> = serde_json::from_str(&text)?;
+
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .map(|raw| raw.data)
+            .collect())
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
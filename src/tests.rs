@@ -1,27 +1,55 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        auth::{ApplicationAuthenticator, Credentials, ScriptAuthenticator},
+        auth::{
+            ApplicationAuthenticator, AuthenticatedClient, Authenticator, CodeFlow, Credentials,
+            FileTokenStore, Scope, ScriptAuthenticator, Token, TokenDuration, TokenStore,
+            UserAgent, UserAuthenticator,
+        },
+        config::Config,
+        flair::{Flair, FlairCsvResult, FlairTemplate},
+        moderation::{ModAction, ModActionType, ModPermission},
+        modmail::Conversation,
         reddit::{Reddit, Result},
+        things::{raw::reports::RawReports, Kind, MultiredditOptions, Reports, Visibility},
     };
 
-    use std::env;
+    use reqwest::blocking::Client;
+
+    use std::{env, sync::Arc};
+
+    // Never logs in, for tests that only parse responses.
+    #[derive(Debug)]
+    struct OfflineAuthenticator;
+
+    impl Authenticator for OfflineAuthenticator {
+        fn login(&self, _client: &Client) -> Result<()> {
+            Ok(())
+        }
+
+        fn token(&self) -> Option<Token> {
+            None
+        }
+
+        fn is_logged_in(&self) -> bool {
+            false
+        }
+
+        fn refresh_token(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn offline_client() -> Arc<AuthenticatedClient> {
+        Arc::new(AuthenticatedClient::new(OfflineAuthenticator, "test:snew:v0.1.0").unwrap())
+    }
 
     #[test]
     fn it_works() -> Result<()> {
-        let username = &env::var("REDDIT_USERNAME").unwrap();
+        let config = Config::from_env();
 
-        let script_auth = ScriptAuthenticator::new(Credentials::new(
-            &env::var("REDDIT_CLIENT_ID").unwrap(),
-            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
-            &env::var("REDDIT_USERNAME").unwrap(),
-            &env::var("REDDIT_PASSWORD").unwrap(),
-        ));
-        let reddit = Reddit::new(
-            script_auth,
-            &format!("Windows:snew:v0.1.0 (by /u/{})", username),
-        )
-        .unwrap();
+        let script_auth = ScriptAuthenticator::new(config.credentials()?);
+        let reddit = Reddit::new(script_auth, config.user_agent()?).unwrap();
 
         println!("{:?}", reddit.me()?);
 
@@ -47,22 +75,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn subreddit_comments() -> Result<()> {
+        let anon_auth = ApplicationAuthenticator::new("h_Il077pxG16K1PahrHvtA");
+
+        let reddit = Reddit::new(anon_auth, "Windows:snew:v0.1.0 (by anonymous)").unwrap();
+
+        for comment in reddit.subreddit("rust").comments().take(3) {
+            let comment = comment?;
+            println!("Comment on {:?}: {}", comment.link_title, comment.body);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn subreddit_about() -> Result<()> {
+        let anon_auth = ApplicationAuthenticator::new("h_Il077pxG16K1PahrHvtA");
+
+        let reddit = Reddit::new(anon_auth, "Windows:snew:v0.1.0 (by anonymous)").unwrap();
+
+        let rust = reddit.subreddit("rust");
+
+        println!("{:?}", rust.about()?);
+
+        for rule in rust.rules()? {
+            println!("Rule: {}", rule.short_name);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn comments() -> Result<()> {
-        let username = &env::var("REDDIT_USERNAME").unwrap();
+        let config = Config::from_env();
 
-        let script_auth = ScriptAuthenticator::new(Credentials::new(
-            &env::var("REDDIT_CLIENT_ID").unwrap(),
-            &env::var("REDDIT_CLIENT_SECRET").unwrap(),
-            username,
-            &env::var("REDDIT_PASSWORD").unwrap(),
-        ));
+        let script_auth = ScriptAuthenticator::new(config.credentials()?);
 
-        let reddit = Reddit::new(
-            script_auth,
-            &format!("Windows:snew:v0.1.0 (by /u/{})", username),
-        )
-        .unwrap();
+        let reddit = Reddit::new(script_auth, config.user_agent()?).unwrap();
 
         let hot = reddit.subreddit("globaloffensive").hot();
 
@@ -108,4 +158,487 @@ mod tests {
 
         reddit.unwrap();
     }
+
+    #[test]
+    fn token_store() -> Result<()> {
+        let path = env::temp_dir().join("snew_token_store_test.json");
+        let store = FileTokenStore::new(&path);
+
+        let token = Token {
+            access_token: String::from("access_token"),
+            expires_in: 3600,
+            scope: String::from("*"),
+            token_type: String::from("bearer"),
+            refresh_token: None,
+            issued_at: 0,
+        };
+
+        let _ = std::fs::remove_file(&path);
+        assert!(store.load()?.is_none());
+
+        // Attaching a store writes the token, with the refresh token filled in.
+        UserAuthenticator::new_complete("refresh_token", "client_id", token)
+            .with_store(store.clone())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                std::fs::metadata(&path)?.permissions().mode() & 0o777,
+                0o600
+            );
+        }
+
+        let stored = store.load()?.unwrap();
+        assert_eq!(stored.access_token, "access_token");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh_token"));
+        assert!(stored.is_expired());
+
+        let user_auth = UserAuthenticator::from_store("client_id", store.clone())?;
+        assert!(user_auth.is_some());
+
+        // A stored token that hasn't expired is used without refreshing it, which would fail here.
+        let mut fresh = stored;
+        fresh.issued_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        store.store(&fresh)?;
+
+        let user_auth = UserAuthenticator::from_store("client_id", store)?.unwrap();
+        let client = AuthenticatedClient::new(user_auth, "test:snew:v0.1.0")?;
+        assert_eq!(
+            client
+                .authenticator
+                .read()
+                .unwrap()
+                .token()
+                .unwrap()
+                .access_token,
+            "access_token"
+        );
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn temporary_token() -> Result<()> {
+        // What a temporary code flow returns: a token without a refresh token.
+        let token = Token {
+            access_token: String::from("access_token"),
+            expires_in: 3600,
+            scope: String::from("identity read"),
+            token_type: String::from("bearer"),
+            refresh_token: None,
+            issued_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        };
+
+        let reddit = Reddit::new(
+            UserAuthenticator::from_token("client_id", token),
+            "test:snew:v0.1.0",
+        )?;
+        assert!(reddit.refresh_token().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scopes() {
+        let mut token = Token {
+            access_token: String::from("access_token"),
+            expires_in: 3600,
+            scope: String::from("identity read"),
+            token_type: String::from("bearer"),
+            refresh_token: None,
+            issued_at: 0,
+        };
+
+        assert_eq!(token.scopes(), vec![Scope::Identity, Scope::Read]);
+        assert!(token.has_scope(Scope::Read));
+        assert!(!token.has_scope(Scope::Submit));
+
+        token.scope = String::from("*");
+        assert!(token.has_scope(Scope::Submit));
+    }
+
+    #[test]
+    fn authorization_url() {
+        let flow = CodeFlow::new("client_id", "https://example.com/callback");
+
+        let url = flow.authorization_url(
+            "state",
+            &[Scope::Identity, Scope::Read],
+            TokenDuration::Temporary,
+        );
+
+        assert_eq!(url, "https://www.reddit.com/api/v1/authorize?client_id=client_id&response_type=code&state=state\
+            &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&duration=temporary&scope=identity+read");
+    }
+
+    #[cfg(feature = "totp")]
+    #[test]
+    fn totp() -> Result<()> {
+        // Test vectors from RFC 6238, truncated to 6 digits. The secret is "12345678901234567890".
+        let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+        assert_eq!(crate::auth::totp(secret, 59)?, "287082");
+        assert_eq!(crate::auth::totp(secret, 1111111109)?, "081804");
+        assert_eq!(crate::auth::totp(secret, 2000000000)?, "279037");
+
+        Ok(())
+    }
+
+    #[cfg(feature = "config_file")]
+    #[test]
+    fn config_file() -> Result<()> {
+        let toml = r#"
+            [default]
+            user_agent = "linux:snew_tests:v0.1.0 (by /u/snew)"
+
+            [bot]
+            client_id = "client_id"
+            client_secret = "client_secret"
+            username = "bot"
+            password = "password"
+
+            [crawler]
+            client_id = "client_id"
+            client_secret = "client_secret"
+        "#;
+
+        let bot = Config::from_toml(toml, "bot")?;
+        assert_eq!(bot.credentials()?.username, "bot");
+        assert_eq!(
+            bot.user_agent()?.as_str(),
+            "linux:snew_tests:v0.1.0 (by /u/snew)"
+        );
+        assert!(bot.authenticator()?.is_logged_in());
+
+        let crawler = Config::from_toml(toml, "crawler")?;
+        assert!(crawler.credentials().is_err());
+        assert!(!crawler.authenticator()?.is_logged_in());
+
+        assert!(Config::from_toml(toml, "missing").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_agent() {
+        let user_agent = UserAgent::builder("snew_tests", "v0.1.0")
+            .platform("linux")
+            .username("/u/snew")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            user_agent.as_str(),
+            format!(
+                "linux:snew_tests:v0.1.0 (by /u/snew) snew/{}",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        assert!(UserAgent::builder("snew:tests", "v0.1.0").build().is_err());
+        assert!(UserAgent::builder("snew_tests", "v0.1.0")
+            .username("not a username")
+            .build()
+            .is_err());
+        assert!(UserAgent::custom("snew æøå").is_err());
+    }
+
+    #[test]
+    fn kinds() -> Result<()> {
+        assert_eq!(Kind::of_fullname("t4_abc123")?, Kind::Message);
+        assert_eq!(Kind::of_fullname("t3_abc123")?.to_string(), "t3");
+        assert!(Kind::of_fullname("t9_abc123").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn multireddit_model() -> Result<()> {
+        let mut options = MultiredditOptions::new("Languages", &["rust", "golang"]);
+        options.visibility = Visibility::Hidden;
+
+        let model: serde_json::Value = serde_json::from_str(&options.model())?;
+
+        assert_eq!(model["display_name"], "Languages");
+        assert_eq!(model["visibility"], "hidden");
+        assert_eq!(model["subreddits"][1]["name"], "golang");
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports() -> Result<()> {
+        let raw: RawReports = serde_json::from_str(
+            r#"{
+                "num_reports": 3,
+                "user_reports": [["Spam", 2, false, false], [null, 1, false, false]],
+                "mod_reports": [["Off topic", "snew"]]
+            }"#,
+        )?;
+        let reports = Reports::from(raw);
+
+        assert_eq!(reports.num_reports, 3);
+        assert_eq!(reports.user_reports[0].reason.as_deref(), Some("Spam"));
+        assert_eq!(reports.user_reports[0].count, 2);
+        assert_eq!(reports.user_reports[1].reason, None);
+        assert_eq!(reports.mod_reports[0].moderator, "snew");
+
+        let raw: RawReports = serde_json::from_str(r#"{"num_reports": null, "user_reports": []}"#)?;
+        assert_eq!(Reports::from(raw).num_reports, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn mod_permissions() {
+        assert_eq!(
+            ModPermission::join(&[ModPermission::Posts, ModPermission::Wiki]),
+            "-all,+posts,+wiki"
+        );
+        assert_eq!(
+            ModPermission::join(&[ModPermission::Mail, ModPermission::All]),
+            "+all"
+        );
+        assert_eq!(ModPermission::join(&[]), "-all");
+    }
+
+    #[test]
+    fn mod_actions() -> Result<()> {
+        let action: ModAction = serde_json::from_str(
+            r#"{
+                "id": "ModAction_abc",
+                "action": "banuser",
+                "mod": "snew",
+                "subreddit": "rust",
+                "target_fullname": "t2_abc123",
+                "details": "permanent",
+                "created_utc": 1625000000.0
+            }"#,
+        )?;
+
+        assert_eq!(action.action, ModActionType::BanUser);
+        assert_eq!(action.moderator, "snew");
+        assert_eq!(action.target_fullname.as_deref(), Some("t2_abc123"));
+
+        let unknown = ModActionType::from(String::from("somethingnew"));
+        assert_eq!(unknown, ModActionType::Other(String::from("somethingnew")));
+        assert_eq!(unknown.as_str(), "somethingnew");
+
+        Ok(())
+    }
+
+    #[test]
+    fn modmail_conversation() -> Result<()> {
+        let conversation = Conversation::parse(
+            r#"{
+                "conversation": {
+                    "id": "abc12",
+                    "subject": "Why was my post removed?",
+                    "owner": {"displayName": "rust", "type": "subreddit"},
+                    "participant": {"name": "someone", "isOp": true, "isParticipant": true},
+                    "isInternal": false,
+                    "isHighlighted": true,
+                    "isAuto": false,
+                    "numMessages": 2,
+                    "lastUpdated": "2021-07-01T12:00:00.000000+00:00",
+                    "objIds": [
+                        {"id": "m2", "key": "messages"},
+                        {"id": "a1", "key": "modActions"},
+                        {"id": "m1", "key": "messages"}
+                    ]
+                },
+                "messages": {
+                    "m1": {
+                        "id": "m1",
+                        "author": {"name": "snew", "isMod": true},
+                        "body": "<p>It was off topic.</p>",
+                        "bodyMarkdown": "It was off topic.",
+                        "isInternal": false,
+                        "date": "2021-07-01T12:00:00.000000+00:00"
+                    },
+                    "m2": {
+                        "id": "m2",
+                        "author": {"name": "someone", "isOp": true},
+                        "body": "<p>Why?</p>",
+                        "bodyMarkdown": "Why?",
+                        "isInternal": false,
+                        "date": "2021-07-01T11:00:00.000000+00:00"
+                    }
+                },
+                "modActions": {
+                    "a1": {
+                        "id": "a1",
+                        "author": {"name": "snew", "isMod": true},
+                        "actionTypeId": 0,
+                        "date": "2021-07-01T11:30:00.000000+00:00"
+                    }
+                }
+            }"#,
+            offline_client(),
+        )?;
+
+        assert_eq!(conversation.subreddit, "rust");
+        assert_eq!(
+            conversation.participant.map(|participant| participant.name),
+            Some(String::from("someone"))
+        );
+
+        // In the order of the ids, not of the map.
+        let ids: Vec<_> = conversation
+            .messages
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, ["m2", "m1"]);
+        assert!(conversation.messages[1].author.is_mod);
+
+        assert_eq!(conversation.mod_actions.len(), 1);
+        assert_eq!(conversation.mod_actions[0].author.name, "snew");
+
+        // Conversations between moderators have an empty participant.
+        let conversations = Conversation::parse_page(
+            r#"{
+                "conversations": {
+                    "def34": {
+                        "id": "def34",
+                        "subject": "Team notes",
+                        "owner": {"displayName": "rust"},
+                        "participant": {},
+                        "isInternal": true,
+                        "isHighlighted": false,
+                        "isAuto": false,
+                        "numMessages": 1,
+                        "lastUpdated": "2021-07-02T12:00:00.000000+00:00",
+                        "objIds": [{"id": "m3", "key": "messages"}]
+                    },
+                    "ghi56": {
+                        "id": "ghi56",
+                        "subject": "Hello",
+                        "owner": {"displayName": "rust"},
+                        "isInternal": false,
+                        "isHighlighted": false,
+                        "isAuto": true,
+                        "numMessages": 0,
+                        "lastUpdated": "2021-07-03T12:00:00.000000+00:00",
+                        "objIds": []
+                    }
+                },
+                "conversationIds": ["ghi56", "def34"],
+                "messages": {
+                    "m3": {
+                        "id": "m3",
+                        "author": {"name": "snew", "isMod": true},
+                        "body": "<p>Note</p>",
+                        "bodyMarkdown": "Note",
+                        "isInternal": true,
+                        "date": "2021-07-02T12:00:00.000000+00:00"
+                    }
+                }
+            }"#,
+            offline_client(),
+        )?;
+
+        assert_eq!(conversations[0].id, "ghi56");
+        assert!(conversations[0].messages.is_empty());
+        assert_eq!(conversations[1].id, "def34");
+        assert!(conversations[1].participant.is_none());
+        assert_eq!(conversations[1].messages[0].body_markdown, "Note");
+
+        Ok(())
+    }
+
+    #[test]
+    fn flair_templates() -> Result<()> {
+        let templates: Vec<FlairTemplate> = serde_json::from_str(
+            r##"[
+                {
+                    "id": "0a1b2c3d-0000-1111-2222-333344445555",
+                    "text": "Tooling",
+                    "css_class": "tooling",
+                    "text_editable": true,
+                    "mod_only": false,
+                    "background_color": "#ff4500",
+                    "text_color": "light",
+                    "type": "text"
+                },
+                {
+                    "id": "6e7f8a9b-0000-1111-2222-333344445555",
+                    "text": "Announcement",
+                    "text_editable": false,
+                    "mod_only": true
+                }
+            ]"##,
+        )?;
+
+        assert_eq!(templates[0].css_class, "tooling");
+        assert!(templates[0].text_editable);
+        assert_eq!(templates[0].background_color, "#ff4500");
+        assert!(templates[1].mod_only);
+        assert_eq!(templates[1].css_class, "");
+        assert_eq!(templates[1].text_color, "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn flair_csv_results() -> Result<()> {
+        let results: Vec<FlairCsvResult> = serde_json::from_str(
+            r#"[
+                {"ok": true, "status": "added flair for user someone", "errors": {}, "warnings": {}},
+                {"ok": false, "status": "skipped", "errors": {"user": "unable to resolve user `nobody', ignoring"}}
+            ]"#,
+        )?;
+
+        assert!(results[0].ok);
+        assert!(results[0].errors.is_empty());
+        assert!(!results[1].ok);
+        assert!(results[1].errors.contains_key("user"));
+        assert!(results[1].warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn flair_request() -> Result<()> {
+        // No template, text or css class clears the flair.
+        let none = Flair::default();
+        let (endpoint, form) = none.request(("name", "someone"));
+        assert_eq!(endpoint, "flair");
+        assert_eq!(form, [("name", "someone"), ("api_type", "json")]);
+
+        let text = Flair::text("Rustacean");
+        let (endpoint, form) = text.request(("name", "someone"));
+        assert_eq!(endpoint, "flair");
+        assert!(form.contains(&("text", "Rustacean")));
+
+        let mut template = Flair::template("0a1b2c3d");
+        template.text = Some(String::from("Tooling"));
+        let (endpoint, form) = template.request(("link", "t3_abc123"));
+        assert_eq!(endpoint, "selectflair");
+        assert!(form.contains(&("link", "t3_abc123")));
+        assert!(form.contains(&("flair_template_id", "0a1b2c3d")));
+        assert!(form.contains(&("text", "Tooling")));
+
+        // Users picking their own flair can't use the flair endpoint, which needs the 'modflair' scope.
+        let (endpoint, form) = none.select_request(("name", "someone"))?;
+        assert_eq!(endpoint, "selectflair");
+        assert_eq!(form, [("name", "someone"), ("api_type", "json")]);
+
+        let (endpoint, form) = template.select_request(("name", "someone"))?;
+        assert_eq!(endpoint, "selectflair");
+        assert!(form.contains(&("flair_template_id", "0a1b2c3d")));
+
+        assert!(text.select_request(("name", "someone")).is_err());
+
+        Ok(())
+    }
 }
@@ -1,10 +1,17 @@
 //! Reddit 'things'. In the API, a thing is a type + fullname.
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 
 use self::raw::{
-    comment::RawCommentData, generic_kind::RawKind, listing::RawListing, post::RawPostData,
+    comment::RawCommentData, generic_kind::RawKind, item::RawItem, listing::RawListing,
+    message::RawMessageData, multi::RawMultiData, post::RawPostData, reports::RawReports,
+};
+use crate::{
+    auth::{AuthenticatedClient, Scope},
+    flair::Flair,
+    inbox::Inbox,
+    reddit::Result,
+    stream::Stream,
 };
-use crate::{auth::AuthenticatedClient, reddit::Result};
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -18,6 +25,10 @@ pub struct Subreddit {
     pub name: String,
     pub url: String,
     pub(crate) client: Arc<AuthenticatedClient>,
+    // Already fetched, e.g. when listing subreddits.
+    pub(crate) about: Option<SubredditAbout>,
+    // Only for r/popular, the region to get posts from.
+    pub(crate) geo_filter: Option<String>,
 }
 
 impl Subreddit {
@@ -28,7 +39,94 @@ impl Subreddit {
             name: String::from(name),
             url: format!("{}/r/{}", crate::reddit::URL, name),
             client,
+            about: None,
+            geo_filter: None,
+        }
+    }
+
+    // The frontpage of the logged in user, or the default subreddits if not logged in.
+    pub(crate) fn frontpage(client: Arc<AuthenticatedClient>) -> Self {
+        Self {
+            name: String::from("frontpage"),
+            url: crate::reddit::URL.to_string(),
+            client,
+            about: None,
+            geo_filter: None,
+        }
+    }
+
+    /// Information about this subreddit, e.g. the number of subscribers and the sidebar.
+    /// If this handle came from a listing of subreddits, the information from the listing is returned without making a request.
+    pub fn about(&self) -> Result<SubredditAbout> {
+        if let Some(about) = &self.about {
+            return Ok(about.clone());
         }
+
+        self.client.require_scope(Scope::Read)?;
+
+        let text = self
+            .client
+            .get(&format!("{}/about", self.url), None::<&()>)?
+            .text()?;
+
+        let about: RawKind<SubredditAbout> = serde_json::from_str(&text)?;
+
+        Ok(about.data)
+    }
+
+    /// Subscribe the logged in user to this subreddit.
+    pub fn subscribe(&self) -> Result<()> {
+        self.set_subscribed("sub")
+    }
+
+    /// Unsubscribe the logged in user from this subreddit.
+    pub fn unsubscribe(&self) -> Result<()> {
+        self.set_subscribed("unsub")
+    }
+
+    fn set_subscribed(&self, action: &str) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        self.client.post(
+            &format!("{}/api/subscribe", crate::reddit::URL),
+            &[
+                ("action", action),
+                ("sr_name", &self.name),
+                // Otherwise subscribing for the first time also subscribes to the default subreddits.
+                ("skip_initial_defaults", "true"),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// The rules of this subreddit.
+    pub fn rules(&self) -> Result<Vec<Rule>> {
+        self.client.require_scope(Scope::Read)?;
+
+        let text = self
+            .client
+            .get(&format!("{}/about/rules", self.url), None::<&()>)?
+            .text()?;
+
+        let rules: RawRules = serde_json::from_str(&text)?;
+
+        Ok(rules.rules)
+    }
+
+    /// The settings of this subreddit. Only visible to moderators with the 'config' permission.
+    pub fn settings(&self) -> Result<SubredditSettings> {
+        self.client.require_scope(Scope::ModConfig)?;
+
+        let text = self
+            .client
+            .get(&format!("{}/about/edit", self.url), None::<&()>)?
+            .text()?;
+
+        let settings: RawKind<SubredditSettings> = serde_json::from_str(&text)?;
+
+        Ok(settings.data)
     }
 
     pub fn hot(&self) -> PostFeed {
@@ -58,6 +156,81 @@ impl Subreddit {
         self.posts_sorted("best")
     }
 
+    /// The latest comments across the subreddit, newest first.
+    /// This works for the frontpage, r/all and combined subreddits too, e.g. `reddit.all().comments()`.
+    pub fn comments(&self) -> CommentListing {
+        CommentListing {
+            limit: 100,
+            url: format!("{}/comments", self.url),
+            client: self.client.clone(),
+            cached_comments: Vec::new(),
+            cursor: Cursor::default(),
+        }
+    }
+
+    /// Posts and comments that were reported or caught by the spam filter, and are waiting for a moderator.
+    /// Only visible to moderators of this subreddit.
+    /// # Usage
+    /// ```no_run
+    /// # fn main() -> snew::reddit::Result<()> {
+    /// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+    /// use snew::things::Item;
+    /// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+    /// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+    ///
+    /// for item in reddit.subreddit("rust").modqueue()?.only_comments() {
+    ///     if let Item::Comment(comment) = item? {
+    ///         for report in &comment.reports.user_reports {
+    ///             println!("{}: {:?} x{}", comment.id, report.reason, report.count);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn modqueue(&self) -> Result<ItemFeed> {
+        self.mod_listing("modqueue")
+    }
+
+    /// Posts and comments that have been reported. Only visible to moderators of this subreddit.
+    pub fn reports(&self) -> Result<ItemFeed> {
+        self.mod_listing("reports")
+    }
+
+    /// Posts and comments that were removed as spam. Only visible to moderators of this subreddit.
+    pub fn spam(&self) -> Result<ItemFeed> {
+        self.mod_listing("spam")
+    }
+
+    /// Posts no moderator has approved or removed yet. Only visible to moderators of this subreddit.
+    pub fn unmoderated(&self) -> Result<ItemFeed> {
+        self.mod_listing("unmoderated")
+    }
+
+    /// Posts and comments that were edited recently. Only visible to moderators of this subreddit.
+    pub fn edited(&self) -> Result<ItemFeed> {
+        self.mod_listing("edited")
+    }
+
+    fn mod_listing(&self, path: &str) -> Result<ItemFeed> {
+        self.client.require_login()?;
+
+        Ok(ItemFeed::create(
+            format!("{}/about/{}", self.url, path),
+            self.client.clone(),
+        ))
+    }
+
+    /// An endless stream of new posts, oldest first. See [`Stream`].
+    pub fn stream_submissions(&self) -> Stream<Post> {
+        Stream::create(format!("{}/new", self.url), self.client.clone())
+    }
+
+    /// An endless stream of new comments, oldest first. See [`Stream`].
+    pub fn stream_comments(&self) -> Stream<Comment> {
+        Stream::create(format!("{}/comments", self.url), self.client.clone())
+    }
+
     // /// Submit a text post.
     // pub fn submit(&self, title: &str, text: &str) -> Post<T> {
     //     self.client.get(
@@ -68,16 +241,316 @@ impl Subreddit {
     // }
 
     fn posts_sorted(&self, path: &str) -> PostFeed {
-        PostFeed {
+        let feed = PostFeed::create(format!("{}/{}", self.url, path), self.client.clone());
+
+        match &self.geo_filter {
+            Some(geo_filter) => feed.with_queries(&[("g", geo_filter)]),
+            None => feed,
+        }
+    }
+}
+
+/// A set of subreddits, meant to be iterated over. The [`Subreddit::about`] information is already filled in.
+/// See [`PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct SubredditFeed {
+    /// The amount of subreddits to request from the Reddit API at once, at most 100.
+    pub limit: i32,
+    url: String,
+    queries: Vec<(String, String)>,
+    cached_subreddits: Vec<Subreddit>,
+    client: Arc<AuthenticatedClient>,
+    cursor: Cursor,
+}
+
+impl SubredditFeed {
+    // Extra queries to send with every request, e.g. a search term.
+    pub(crate) fn with_queries(mut self, queries: &[(&str, &str)]) -> Self {
+        self.queries = queries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        self
+    }
+
+    pub(crate) fn create(url: String, client: Arc<AuthenticatedClient>) -> Self {
+        Self {
             limit: 100,
-            url: format!("{}/{}", self.url, path),
-            cached_posts: Vec::new(),
-            client: self.client.clone(),
-            after: String::from(""),
+            url,
+            queries: Vec::new(),
+            cached_subreddits: Vec::new(),
+            client,
+            cursor: Cursor::default(),
         }
     }
 }
 
+impl Iterator for SubredditFeed {
+    type Item = Result<Subreddit>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_subreddits.pop().map(Ok).or_else_transpose(|| {
+            let children: Vec<RawKind<SubredditAbout>> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &self.queries)?;
+
+            let client = &self.client;
+
+            self.cached_subreddits
+                .extend(children.into_iter().rev().map(|raw| {
+                    let mut subreddit = Subreddit::create(&raw.data.display_name, client.clone());
+                    subreddit.about = Some(raw.data);
+                    subreddit
+                }));
+
+            Ok(self.cached_subreddits.pop())
+        })
+    }
+}
+
+/// A multireddit, a named collection of subreddits whose posts are ranked together.
+/// # Usage
+/// ```no_run
+/// # fn main() -> snew::reddit::Result<()> {
+/// # use snew::{reddit::Reddit, auth::{ScriptAuthenticator, Credentials}};
+/// # let script_auth = ScriptAuthenticator::new(Credentials::new("client_id", "client_secret", "username", "password"));
+/// # let reddit = Reddit::new(script_auth, "<Operating system>:snew:v0.1.0 (by u/<reddit username>)")?;
+/// for multireddit in reddit.my_multireddits()? {
+///     println!("{}: {}", multireddit.display_name, multireddit.subreddits.join(", "));
+///
+///     for post in multireddit.hot().take(5) {
+///         println!("    {}", post?.title);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Multireddit {
+    client: Arc<AuthenticatedClient>,
+    /// The name used in the path.
+    pub name: String,
+    pub display_name: String,
+    /// The user this multireddit belongs to.
+    pub owner: String,
+    /// E.g. /user/spez/m/favorites.
+    pub path: String,
+    /// In markdown.
+    pub description: String,
+    pub visibility: Visibility,
+    /// The names of the subreddits in this multireddit.
+    pub subreddits: Vec<String>,
+    pub nsfw: bool,
+    /// Whether the logged in user can edit this multireddit.
+    pub can_edit: bool,
+    /// When this multireddit was created, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+}
+
+impl Multireddit {
+    pub fn hot(&self) -> PostFeed {
+        self.posts_sorted("hot")
+    }
+
+    // See Subreddit::new.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(&self) -> PostFeed {
+        self.posts_sorted("new")
+    }
+
+    pub fn rising(&self) -> PostFeed {
+        self.posts_sorted("rising")
+    }
+
+    pub fn top(&self) -> PostFeed {
+        self.posts_sorted("top")
+    }
+
+    pub fn best(&self) -> PostFeed {
+        self.posts_sorted("best")
+    }
+
+    /// Handles to the subreddits in this multireddit.
+    pub fn subreddit_handles(&self) -> Vec<Subreddit> {
+        self.subreddits
+            .iter()
+            .map(|name| Subreddit::create(name, self.client.clone()))
+            .collect()
+    }
+
+    /// Replace the name, description, visibility and subreddits of this multireddit.
+    pub fn update(&mut self, options: &MultiredditOptions) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        let text = self
+            .client
+            .put(&self.api_url(), &[("model", options.model())])?
+            .text()?;
+
+        *self = Self::parse(&text, self.client.clone())?;
+
+        Ok(())
+    }
+
+    /// Add a subreddit to this multireddit.
+    pub fn add_subreddit(&mut self, subreddit: &str) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        self.client.put(
+            &format!("{}/r/{}", self.api_url(), subreddit),
+            &[(
+                "model",
+                serde_json::json!({ "name": subreddit }).to_string(),
+            )],
+        )?;
+
+        self.subreddits.push(String::from(subreddit));
+
+        Ok(())
+    }
+
+    /// Remove a subreddit from this multireddit.
+    pub fn remove_subreddit(&mut self, subreddit: &str) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        self.client
+            .delete(&format!("{}/r/{}", self.api_url(), subreddit))?;
+
+        self.subreddits
+            .retain(|name| !name.eq_ignore_ascii_case(subreddit));
+
+        Ok(())
+    }
+
+    /// Delete this multireddit.
+    pub fn delete(self) -> Result<()> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        self.client.delete(&self.api_url())?;
+
+        Ok(())
+    }
+
+    /// Copy this multireddit, which may belong to someone else, to the logged in user under `name`.
+    pub fn copy(&self, name: &str) -> Result<Multireddit> {
+        self.client.require_login()?;
+        self.client.require_scope(Scope::Subscribe)?;
+
+        let to = format!("/user/{}/m/{}", username(&self.client)?, name);
+
+        let text = self
+            .client
+            .post(
+                &format!("{}/api/multi/copy", crate::reddit::URL),
+                &[
+                    ("from", self.path.as_str()),
+                    ("to", &to),
+                    ("display_name", name),
+                ],
+            )?
+            .text()?;
+
+        Self::parse(&text, self.client.clone())
+    }
+
+    pub(crate) fn parse(text: &str, client: Arc<AuthenticatedClient>) -> Result<Self> {
+        let raw: RawKind<RawMultiData> = serde_json::from_str(text)?;
+
+        Ok((raw, client).into())
+    }
+
+    pub(crate) fn parse_all(text: &str, client: Arc<AuthenticatedClient>) -> Result<Vec<Self>> {
+        let raw: Vec<RawKind<RawMultiData>> = serde_json::from_str(text)?;
+
+        Ok(raw
+            .into_iter()
+            .map(|raw| (raw, client.clone()).into())
+            .collect())
+    }
+
+    fn api_url(&self) -> String {
+        format!(
+            "{}/api/multi{}",
+            crate::reddit::URL,
+            self.path.trim_end_matches('/')
+        )
+    }
+
+    fn posts_sorted(&self, path: &str) -> PostFeed {
+        PostFeed::create(
+            format!(
+                "{}{}/{}",
+                crate::reddit::URL,
+                self.path.trim_end_matches('/'),
+                path
+            ),
+            self.client.clone(),
+        )
+    }
+}
+
+/// Who can see a multireddit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Private,
+    Public,
+    /// Public, but not shown on the owner's profile.
+    Hidden,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Private => "private",
+            Self::Public => "public",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+/// What a multireddit should look like, for [`crate::reddit::Reddit::create_multireddit`] and [`Multireddit::update`].
+#[derive(Debug, Clone)]
+pub struct MultiredditOptions {
+    pub display_name: String,
+    /// In markdown.
+    pub description: String,
+    pub visibility: Visibility,
+    /// The names of the subreddits, without the r/.
+    pub subreddits: Vec<String>,
+}
+
+impl MultiredditOptions {
+    /// A private multireddit without a description.
+    pub fn new(display_name: &str, subreddits: &[&str]) -> Self {
+        Self {
+            display_name: String::from(display_name),
+            description: String::new(),
+            visibility: Visibility::Private,
+            subreddits: subreddits.iter().map(|name| String::from(*name)).collect(),
+        }
+    }
+
+    // Reddit takes the multireddit as JSON in a form field.
+    pub(crate) fn model(&self) -> String {
+        serde_json::json!({
+            "display_name": self.display_name,
+            "description_md": self.description,
+            "visibility": self.visibility.as_str(),
+            "subreddits": self
+                .subreddits
+                .iter()
+                .map(|name| serde_json::json!({ "name": name }))
+                .collect::<Vec<_>>(),
+        })
+        .to_string()
+    }
+}
+
 /// A post.
 #[derive(Debug, Clone)]
 pub struct Post {
@@ -103,6 +576,8 @@ pub struct Post {
     pub id: String,
     /// The 'kind'. This should always be t3. Combine with [`Self::id`] to get the fullname of this post.
     pub kind: String,
+    /// Only visible to moderators.
+    pub reports: Reports,
 }
 
 impl Post {
@@ -121,6 +596,134 @@ impl Post {
         }
     }
 
+    /// The fullname, e.g. t3_abc123.
+    pub fn fullname(&self) -> String {
+        format!("{}_{}", self.kind, self.id)
+    }
+
+    /// Set the flair of this post. Moderators can set any flair, the author only flair from templates that aren't mod only.
+    pub fn set_flair(&self, flair: &Flair) -> Result<()> {
+        Subreddit::create(&self.subreddit, self.client.clone())
+            .set_post_flair(&self.fullname(), flair)
+    }
+
+    /// Approve this post, removing it from the mod queue. Only allowed for moderators of the subreddit, like the other moderator actions.
+    pub fn approve(&self) -> Result<()> {
+        moderate(&self.client, "approve", &self.fullname(), &[])
+    }
+
+    /// Remove this post. If `spam` is true, it also trains the spam filter.
+    pub fn remove(&self, spam: bool) -> Result<()> {
+        moderate(
+            &self.client,
+            "remove",
+            &self.fullname(),
+            &[("spam", &spam.to_string())],
+        )
+    }
+
+    /// Remove this post, and attach a removal reason from the subreddit's list, a note for the other moderators, or both.
+    pub fn remove_with_reason(
+        &self,
+        spam: bool,
+        reason_id: Option<&str>,
+        mod_note: Option<&str>,
+    ) -> Result<()> {
+        self.remove(spam)?;
+        add_removal_reason(&self.client, &self.fullname(), reason_id, mod_note)
+    }
+
+    /// Lock this post, so no new comments can be made.
+    pub fn lock(&self) -> Result<()> {
+        moderate(&self.client, "lock", &self.fullname(), &[])
+    }
+
+    pub fn unlock(&self) -> Result<()> {
+        moderate(&self.client, "unlock", &self.fullname(), &[])
+    }
+
+    /// Sticky this post to the top of the subreddit. `slot` is 1 or 2, if None it replaces the bottom sticky.
+    pub fn sticky(&self, slot: Option<u8>) -> Result<()> {
+        let slot = slot.map(|slot| slot.to_string());
+        let mut form = vec![("state", "true")];
+
+        if let Some(slot) = &slot {
+            form.push(("num", slot));
+        }
+
+        moderate(
+            &self.client,
+            "set_subreddit_sticky",
+            &self.fullname(),
+            &form,
+        )
+    }
+
+    pub fn unsticky(&self) -> Result<()> {
+        moderate(
+            &self.client,
+            "set_subreddit_sticky",
+            &self.fullname(),
+            &[("state", "false")],
+        )
+    }
+
+    /// Mark this post as made by a moderator, with a green [M].
+    pub fn distinguish(&self) -> Result<()> {
+        moderate(
+            &self.client,
+            "distinguish",
+            &self.fullname(),
+            &[("how", "yes")],
+        )
+    }
+
+    pub fn undistinguish(&self) -> Result<()> {
+        moderate(
+            &self.client,
+            "distinguish",
+            &self.fullname(),
+            &[("how", "no")],
+        )
+    }
+
+    pub fn mark_nsfw(&self) -> Result<()> {
+        moderate(&self.client, "marknsfw", &self.fullname(), &[])
+    }
+
+    pub fn unmark_nsfw(&self) -> Result<()> {
+        moderate(&self.client, "unmarknsfw", &self.fullname(), &[])
+    }
+
+    pub fn mark_spoiler(&self) -> Result<()> {
+        moderate(&self.client, "spoiler", &self.fullname(), &[])
+    }
+
+    pub fn unmark_spoiler(&self) -> Result<()> {
+        moderate(&self.client, "unspoiler", &self.fullname(), &[])
+    }
+
+    /// The sort the comments of this post are shown in by default. None clears it.
+    pub fn set_suggested_sort(&self, sort: Option<CommentSort>) -> Result<()> {
+        moderate(
+            &self.client,
+            "set_suggested_sort",
+            &self.fullname(),
+            // Reddit calls no suggested sort "blank".
+            &[("sort", sort.map_or("blank", |sort| sort.as_str()))],
+        )
+    }
+
+    /// In contest mode, comments are shown in a random order and their scores are hidden.
+    pub fn set_contest_mode(&self, enabled: bool) -> Result<()> {
+        moderate(
+            &self.client,
+            "set_contest_mode",
+            &self.fullname(),
+            &[("state", &enabled.to_string())],
+        )
+    }
+
     #[cfg(feature = "parse_content")]
     #[cfg_attr(docsrs, doc(cfg(feature = "parse_content")))]
     pub fn get_content(&self) -> Result<Content> {
@@ -143,26 +746,48 @@ pub struct PostFeed {
     /// which is 100, the max Reddit allows.
     pub limit: i32,
     url: String,
+    queries: Vec<(String, String)>,
     cached_posts: Vec<Post>,
     client: Arc<AuthenticatedClient>,
     after: String,
 }
 
+impl PostFeed {
+    // Extra queries to send with every request, e.g. a geo filter.
+    pub(crate) fn with_queries(mut self, queries: &[(&str, &str)]) -> Self {
+        self.queries = queries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        self
+    }
+
+    pub(crate) fn create(url: String, client: Arc<AuthenticatedClient>) -> Self {
+        Self {
+            limit: 100,
+            url,
+            queries: Vec::new(),
+            cached_posts: Vec::new(),
+            client,
+            after: String::from(""),
+        }
+    }
+}
+
 impl Iterator for PostFeed {
     type Item = Result<Post>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.cached_posts.pop().map(Ok).or_else_transpose(|| {
-            let text = self
-                .client
-                .get(
-                    &self.url,
-                    Some(&[
-                        ("limit", self.limit.to_string()),
-                        ("after", self.after.clone()),
-                    ]),
-                )?
-                .text()?;
+            self.client.require_scope(Scope::Read)?;
+
+            let mut queries = vec![
+                (String::from("limit"), self.limit.to_string()),
+                (String::from("after"), self.after.clone()),
+            ];
+            queries.extend(self.queries.iter().cloned());
+
+            let text = self.client.get(&self.url, Some(&queries))?.text()?;
 
             let listing: RawListing<RawKind<RawPostData>> = serde_json::from_str(&text)?;
 
@@ -188,12 +813,310 @@ impl Iterator for PostFeed {
     }
 }
 
+/// Either a post or a comment, e.g. from [`Subreddit::modqueue`].
+#[derive(Debug)]
+pub enum Item {
+    Post(Post),
+    Comment(Comment),
+}
+
+impl Item {
+    /// The fullname, e.g. t3_abc123.
+    pub fn fullname(&self) -> String {
+        match self {
+            Self::Post(post) => post.fullname(),
+            Self::Comment(comment) => comment.fullname(),
+        }
+    }
+
+    pub fn subreddit(&self) -> &str {
+        match self {
+            Self::Post(post) => &post.subreddit,
+            Self::Comment(comment) => &comment.subreddit,
+        }
+    }
+
+    pub fn reports(&self) -> &Reports {
+        match self {
+            Self::Post(post) => &post.reports,
+            Self::Comment(comment) => &comment.reports,
+        }
+    }
+}
+
+/// A set of posts and comments, meant to be iterated over. See [`PostFeed`] for how the limit works.
+#[derive(Debug)]
+pub struct ItemFeed {
+    /// The amount of items to request from the Reddit API at once, at most 100.
+    pub limit: i32,
+    url: String,
+    only: Option<&'static str>,
+    cached_items: Vec<Item>,
+    client: Arc<AuthenticatedClient>,
+    cursor: Cursor,
+}
+
+impl ItemFeed {
+    pub(crate) fn create(url: String, client: Arc<AuthenticatedClient>) -> Self {
+        Self {
+            limit: 100,
+            url,
+            only: None,
+            cached_items: Vec::new(),
+            client,
+            cursor: Cursor::default(),
+        }
+    }
+
+    /// Only iterate over posts.
+    pub fn only_posts(mut self) -> Self {
+        self.only = Some("links");
+        self
+    }
+
+    /// Only iterate over comments.
+    pub fn only_comments(mut self) -> Self {
+        self.only = Some("comments");
+        self
+    }
+}
+
+impl Iterator for ItemFeed {
+    type Item = Result<Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_items.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::Read)?;
+
+            let mut queries = Vec::new();
+
+            if let Some(only) = self.only {
+                queries.push((String::from("only"), String::from(only)));
+            }
+
+            let children: Vec<RawItem> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &queries)?;
+
+            let client = &self.client;
+
+            self.cached_items
+                .extend(children.into_iter().rev().map(|raw| {
+                    match raw {
+                        RawItem::Post(data) => Item::Post(
+                            (
+                                RawKind {
+                                    data,
+                                    kind: Kind::Link.to_string(),
+                                },
+                                client.clone(),
+                            )
+                                .into(),
+                        ),
+                        RawItem::Comment(data) => Item::Comment(
+                            (
+                                RawKind {
+                                    data,
+                                    kind: Kind::Comment.to_string(),
+                                },
+                                client.clone(),
+                            )
+                                .into(),
+                        ),
+                    }
+                }));
+
+            Ok(self.cached_items.pop())
+        })
+    }
+}
+
+/// Reports on a post or comment. Empty unless the logged in user moderates the subreddit.
+#[derive(Debug, Clone, Default)]
+pub struct Reports {
+    /// The total number of reports.
+    pub num_reports: u32,
+    pub user_reports: Vec<UserReport>,
+    pub mod_reports: Vec<ModReport>,
+}
+
+/// A reason users reported something for, and how many did.
+#[derive(Debug, Clone)]
+pub struct UserReport {
+    /// None if the reporter gave no reason.
+    pub reason: Option<String>,
+    pub count: u32,
+}
+
+/// A report by a moderator.
+#[derive(Debug, Clone)]
+pub struct ModReport {
+    pub reason: Option<String>,
+    pub moderator: String,
+}
+
+// Reports come as arrays, [reason, count, ...] for users and [reason, moderator] for moderators.
+impl From<RawReports> for Reports {
+    fn from(raw: RawReports) -> Self {
+        Self {
+            num_reports: raw.num_reports.unwrap_or(0),
+            user_reports: raw
+                .user_reports
+                .unwrap_or_default()
+                .into_iter()
+                .map(|report| UserReport {
+                    reason: report
+                        .first()
+                        .and_then(|reason| reason.as_str())
+                        .map(String::from),
+                    count: report.get(1).and_then(|count| count.as_u64()).unwrap_or(1) as u32,
+                })
+                .collect(),
+            mod_reports: raw
+                .mod_reports
+                .unwrap_or_default()
+                .into_iter()
+                .map(|report| ModReport {
+                    reason: report
+                        .first()
+                        .and_then(|reason| reason.as_str())
+                        .map(String::from),
+                    moderator: report
+                        .get(1)
+                        .and_then(|moderator| moderator.as_str())
+                        .map(String::from)
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
 /// A comment.
 #[derive(Debug)]
 pub struct Comment {
+    client: Arc<AuthenticatedClient>,
     pub author: String,
     pub body: String,
+    /// The unique base 36 ID of this comment
     pub id: String,
+    /// The 'kind'. This should always be t1. Combine with [`Self::id`] to get the fullname of this comment.
+    pub kind: String,
+    /// The score. Upvotes - downvotes.
+    pub score: i32,
+    /// The subreddit name this comment belongs to
+    pub subreddit: String,
+    /// The fullname of the post this comment belongs to, e.g. t3_abc123.
+    pub link_id: String,
+    /// The title of the post this comment belongs to. Only included in subreddit-wide listings, see [`Subreddit::comments`].
+    pub link_title: Option<String>,
+    /// The fullname of the parent, either the post or another comment.
+    pub parent_id: String,
+    /// Link to this comment, relative to reddit.com.
+    pub permalink: String,
+    /// When this comment was created, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+    /// Only visible to moderators.
+    pub reports: Reports,
+}
+
+/// The latest comments across a subreddit, newest first. Meant to be iterated over, see [`Subreddit::comments`].
+/// Like [`PostFeed`], this will keep going as long as there are comments, you may wish to take() some elements.
+#[derive(Debug)]
+pub struct CommentListing {
+    /// The amount of comments to request from the Reddit API at once, at most 100. See [`PostFeed::limit`].
+    pub limit: i32,
+    url: String,
+    client: Arc<AuthenticatedClient>,
+    cached_comments: Vec<Comment>,
+    cursor: Cursor,
+}
+
+impl Iterator for CommentListing {
+    type Item = Result<Comment>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cached_comments.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::Read)?;
+
+            let children: Vec<RawKind<RawCommentData>> =
+                self.cursor
+                    .next_page(&self.client, &self.url, self.limit, &[])?;
+
+            let client = &self.client;
+
+            self.cached_comments.extend(
+                children
+                    .into_iter()
+                    .rev()
+                    .map(|raw| (raw, client.clone()).into()),
+            );
+
+            Ok(self.cached_comments.pop())
+        })
+    }
+}
+
+impl Comment {
+    /// The fullname, e.g. t1_abc123.
+    pub fn fullname(&self) -> String {
+        format!("{}_{}", self.kind, self.id)
+    }
+
+    /// Approve this comment, removing it from the mod queue. Only allowed for moderators of the subreddit, like the other moderator actions.
+    pub fn approve(&self) -> Result<()> {
+        moderate(&self.client, "approve", &self.fullname(), &[])
+    }
+
+    /// Remove this comment. If `spam` is true, it also trains the spam filter.
+    pub fn remove(&self, spam: bool) -> Result<()> {
+        moderate(
+            &self.client,
+            "remove",
+            &self.fullname(),
+            &[("spam", &spam.to_string())],
+        )
+    }
+
+    /// Remove this comment, and attach a removal reason from the subreddit's list, a note for the other moderators, or both.
+    pub fn remove_with_reason(
+        &self,
+        spam: bool,
+        reason_id: Option<&str>,
+        mod_note: Option<&str>,
+    ) -> Result<()> {
+        self.remove(spam)?;
+        add_removal_reason(&self.client, &self.fullname(), reason_id, mod_note)
+    }
+
+    /// Lock this comment, so no one can reply to it.
+    pub fn lock(&self) -> Result<()> {
+        moderate(&self.client, "lock", &self.fullname(), &[])
+    }
+
+    pub fn unlock(&self) -> Result<()> {
+        moderate(&self.client, "unlock", &self.fullname(), &[])
+    }
+
+    /// Mark this comment as made by a moderator, with a green [M]. If `sticky` is true, it is also stickied to the top of the post.
+    /// Only top level comments made by the logged in user can be stickied.
+    pub fn distinguish(&self, sticky: bool) -> Result<()> {
+        moderate(
+            &self.client,
+            "distinguish",
+            &self.fullname(),
+            &[("how", "yes"), ("sticky", &sticky.to_string())],
+        )
+    }
+
+    pub fn undistinguish(&self) -> Result<()> {
+        moderate(
+            &self.client,
+            "distinguish",
+            &self.fullname(),
+            &[("how", "no")],
+        )
+    }
 }
 
 /// A set of comments, meant to be iterated over.
@@ -208,6 +1131,8 @@ impl Iterator for CommentFeed {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.cached_comments.pop().map(Ok).or_else_transpose(|| {
+            self.client.require_scope(Scope::Read)?;
+
             let text = self.client.get(&self.url, None::<&()>)?.text()?;
 
             // The first listing returned by reddit is the post the comments belong to (smh..), the second listing are the comments.
@@ -216,14 +1141,223 @@ impl Iterator for CommentFeed {
                 serde_json::from_str(&text)?;
 
             // Add comments to the cached_commments array, converting from RawComment to Comment in the process
-            self.cached_comments
-                .extend(listings.1.data.children.into_iter().rev().map(From::from));
+            let client = &self.client;
+
+            self.cached_comments.extend(
+                listings
+                    .1
+                    .data
+                    .children
+                    .into_iter()
+                    .rev()
+                    .map(|raw| (raw, client.clone()).into()),
+            );
 
             Ok(self.cached_comments.pop())
         })
     }
 }
 
+/// A message in the inbox. Besides private messages (kind t4), Reddit puts comment replies and username mentions (kind t1) in the inbox as messages too.
+#[derive(Debug, Clone)]
+pub struct Message {
+    client: Arc<AuthenticatedClient>,
+    /// The unique base 36 ID of this message
+    pub id: String,
+    /// The 'kind'. t4 for private messages, t1 for comment replies and mentions. Combine with [`Self::id`] to get the fullname of this message.
+    pub kind: String,
+    /// The author. None for e.g. messages from a subreddit.
+    pub author: Option<String>,
+    /// The recipient, a username, or a subreddit prefixed with #.
+    pub dest: String,
+    pub subject: String,
+    pub body: String,
+    /// The subreddit the message was sent from, or the comment was made in.
+    pub subreddit: Option<String>,
+    /// Whether this message is unread.
+    pub new: bool,
+    /// Whether this is a comment reply or mention, rather than a private message.
+    pub was_comment: bool,
+    /// Link to the comment, for comment replies and mentions.
+    pub context: Option<String>,
+    /// The title of the post, for comment replies and mentions.
+    pub link_title: Option<String>,
+    /// The fullname of the parent message or comment, if any.
+    pub parent_id: Option<String>,
+    /// When this message was sent, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+}
+
+impl Message {
+    /// The fullname, e.g. t4_abc123.
+    pub fn fullname(&self) -> String {
+        format!("{}_{}", self.kind, self.id)
+    }
+
+    /// Whether this is a private message, rather than a comment reply or mention.
+    pub fn is_private_message(&self) -> bool {
+        self.kind == Kind::Message.prefix()
+    }
+
+    /// Reply to this message, returning the reply. For comment replies and mentions, this replies with a comment instead.
+    pub fn reply(&self, body: &str) -> Result<MessageReply> {
+        self.client.require_scope(if self.is_private_message() {
+            Scope::PrivateMessages
+        } else {
+            Scope::Submit
+        })?;
+
+        let response = self.client.post_api(
+            &format!("{}/api/comment", crate::reddit::URL),
+            &[
+                ("api_type", "json"),
+                ("thing_id", &self.fullname()),
+                ("text", body),
+            ],
+        )?;
+
+        let things = response["data"]["things"].clone();
+
+        let reply = if self.is_private_message() {
+            let mut things: Vec<RawKind<RawMessageData>> = serde_json::from_value(things)?;
+            things
+                .pop()
+                .map(|raw| MessageReply::Message((raw, self.client.clone()).into()))
+        } else {
+            let mut things: Vec<RawKind<RawCommentData>> = serde_json::from_value(things)?;
+            things
+                .pop()
+                .map(|raw| MessageReply::Comment((raw, self.client.clone()).into()))
+        };
+
+        reply.ok_or_else(|| {
+            crate::reddit::Error::ApiError(String::from("Reddit did not return the reply"))
+        })
+    }
+
+    /// Delete this message from your inbox. Only works for private messages.
+    pub fn delete(&self) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client.post(
+            &format!("{}/api/del_msg", crate::reddit::URL),
+            &[("id", self.fullname())],
+        )?;
+
+        Ok(())
+    }
+
+    /// Block the author of this message, so they can't message you anymore.
+    pub fn block_author(&self) -> Result<()> {
+        self.client.require_scope(Scope::PrivateMessages)?;
+
+        self.client.post(
+            &format!("{}/api/block", crate::reddit::URL),
+            &[("id", self.fullname())],
+        )?;
+
+        Ok(())
+    }
+
+    /// Mark this message as read.
+    pub fn mark_read(&self) -> Result<()> {
+        Inbox::create(self.client.clone()).mark_read(&[&self.fullname()])
+    }
+
+    /// Mark this message as unread.
+    pub fn mark_unread(&self) -> Result<()> {
+        Inbox::create(self.client.clone()).mark_unread(&[&self.fullname()])
+    }
+}
+
+/// A reply to a [`Message`], see [`Message::reply`].
+#[derive(Debug)]
+pub enum MessageReply {
+    /// The reply to a private message.
+    Message(Message),
+    /// The reply to a comment reply or mention.
+    Comment(Comment),
+}
+
+/// Information about a subreddit, see [`Subreddit::about`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubredditAbout {
+    /// The unique base 36 ID of this subreddit
+    pub id: String,
+    /// The fullname, e.g. t5_2qh1i.
+    pub name: String,
+    /// The name of the subreddit, without the r/.
+    pub display_name: String,
+    pub title: String,
+    /// The short description shown e.g. in search results.
+    pub public_description: String,
+    /// The sidebar, in markdown.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// None if hidden, e.g. for private subreddits.
+    #[serde(default)]
+    pub subscribers: Option<u64>,
+    /// Users active right now. None if hidden.
+    #[serde(default)]
+    pub active_user_count: Option<u64>,
+    /// When this subreddit was created, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+    /// Whether this subreddit is NSFW.
+    #[serde(default)]
+    pub over18: Option<bool>,
+    /// public, private, restricted, gold_restricted, archived, employees_only, gold_only or user.
+    pub subreddit_type: String,
+    /// What can be posted: any, link or self.
+    #[serde(default)]
+    pub submission_type: Option<String>,
+    #[serde(default)]
+    pub icon_img: Option<String>,
+    #[serde(default)]
+    pub community_icon: Option<String>,
+    #[serde(default)]
+    pub banner_img: Option<String>,
+    #[serde(default)]
+    pub header_img: Option<String>,
+    /// Relative to reddit.com, e.g. /r/rust/.
+    pub url: String,
+}
+
+/// A rule of a subreddit, see [`Subreddit::rules`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub short_name: String,
+    /// In markdown.
+    pub description: String,
+    /// What this rule applies to: link, comment or all.
+    pub kind: String,
+    /// The reason shown when reporting something for breaking this rule.
+    pub violation_reason: String,
+    pub priority: i32,
+    /// When this rule was created, in seconds since the UNIX epoch.
+    pub created_utc: f64,
+}
+
+/// The settings of a subreddit, see [`Subreddit::settings`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubredditSettings {
+    pub title: String,
+    pub public_description: String,
+    /// The sidebar, in markdown.
+    pub description: String,
+    /// Text shown on the submission page.
+    pub submit_text: String,
+    /// public, private, restricted etc.
+    pub subreddit_type: String,
+    /// What can be posted: any, link or self.
+    pub link_type: String,
+    pub over_18: bool,
+    pub spoilers_enabled: bool,
+    pub language: String,
+    /// Any other settings Reddit returned.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
 /// Information about the authenticated user
 #[derive(Debug, Deserialize)]
 pub struct Me {
@@ -258,17 +1392,93 @@ impl From<(RawKind<RawPostData>, Arc<AuthenticatedClient>)> for Post {
             selftext,
             id: raw.data.id,
             kind: raw.kind,
+            reports: raw.data.reports.into(),
+        }
+    }
+}
+
+// Create a multireddit from some raw data.
+impl From<(RawKind<RawMultiData>, Arc<AuthenticatedClient>)> for Multireddit {
+    fn from(raw: (RawKind<RawMultiData>, Arc<AuthenticatedClient>)) -> Self {
+        let (raw, client) = raw;
+
+        Self {
+            client,
+            name: raw.data.name,
+            display_name: raw.data.display_name,
+            owner: raw.data.owner,
+            path: raw.data.path,
+            description: raw.data.description_md,
+            visibility: raw.data.visibility,
+            subreddits: raw
+                .data
+                .subreddits
+                .into_iter()
+                .map(|subreddit| subreddit.name)
+                .collect(),
+            nsfw: raw.data.over_18,
+            can_edit: raw.data.can_edit,
+            created_utc: raw.data.created_utc,
+        }
+    }
+}
+
+// The name of the logged in user.
+pub(crate) fn username(client: &AuthenticatedClient) -> Result<String> {
+    client.require_scope(Scope::Identity)?;
+
+    let me: Me = serde_json::from_str(
+        &client
+            .get(&format!("{}/api/v1/me", crate::reddit::URL), None::<&()>)?
+            .text()?,
+    )?;
+
+    Ok(me.name)
+}
+
+// Create a message from som raw data.
+impl From<(RawKind<RawMessageData>, Arc<AuthenticatedClient>)> for Message {
+    fn from(raw: (RawKind<RawMessageData>, Arc<AuthenticatedClient>)) -> Self {
+        let (raw, client) = raw;
+
+        Self {
+            client,
+            id: raw.data.id,
+            kind: raw.kind,
+            author: raw.data.author,
+            dest: raw.data.dest,
+            subject: raw.data.subject,
+            body: raw.data.body,
+            subreddit: raw.data.subreddit,
+            new: raw.data.new,
+            was_comment: raw.data.was_comment,
+            context: raw.data.context.filter(|context| !context.is_empty()),
+            link_title: raw.data.link_title,
+            parent_id: raw.data.parent_id,
+            created_utc: raw.data.created_utc,
         }
     }
 }
 
 // Create a comment from som raw data.
-impl From<RawKind<RawCommentData>> for Comment {
-    fn from(raw: RawKind<RawCommentData>) -> Self {
+impl From<(RawKind<RawCommentData>, Arc<AuthenticatedClient>)> for Comment {
+    fn from(raw: (RawKind<RawCommentData>, Arc<AuthenticatedClient>)) -> Self {
+        let (raw, client) = raw;
+
         Self {
+            client,
             author: raw.data.author,
             id: raw.data.id,
             body: raw.data.body,
+            kind: raw.kind,
+            score: raw.data.score,
+            subreddit: raw.data.subreddit,
+            link_id: raw.data.link_id,
+            link_title: raw.data.link_title,
+            parent_id: raw.data.parent_id,
+            permalink: raw.data.permalink,
+            created_utc: raw.data.created_utc,
+            reports: raw.data.reports.into(),
         }
     }
 }
@@ -287,26 +1497,179 @@ impl<T> Transpose<T> for Option<Result<T>> {
     }
 }
 
-// Not used yet
-// pub enum Kind {
-//     Comment,
-//     Account,
-//     Link,
-//     Message,
-//     Subreddit,
-//     Award,
-// }
+// Where a listing left off. Reddit sets `after` to null on the last page.
+#[derive(Debug, Default)]
+pub(crate) struct Cursor {
+    after: String,
+    done: bool,
+}
+
+impl Cursor {
+    // The children of the next page of the listing at `url`. Empty once the last page has been fetched.
+    pub(crate) fn next_page<T: DeserializeOwned>(
+        &mut self,
+        client: &AuthenticatedClient,
+        url: &str,
+        limit: i32,
+        queries: &[(String, String)],
+    ) -> Result<Vec<T>> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+
+        let mut all_queries = vec![
+            (String::from("limit"), limit.to_string()),
+            (String::from("after"), self.after.clone()),
+        ];
+        all_queries.extend(queries.iter().cloned());
+
+        let text = client.get(url, Some(&all_queries))?.text()?;
+
+        let listing: RawListing<T> = serde_json::from_str(&text)?;
+
+        match listing.data.pagination.after {
+            Some(after) => self.after = after,
+            None => self.done = true,
+        }
+
+        Ok(listing.data.children)
+    }
+}
+
+/// The type of a thing. Combined with the ID of the thing, the prefix makes up the fullname, e.g. t3_abc123 for a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// t1
+    Comment,
+    /// t2
+    Account,
+    /// t3, a post.
+    Link,
+    /// t4, a private message.
+    Message,
+    /// t5
+    Subreddit,
+    /// t6
+    Award,
+}
+
+impl Kind {
+    /// The prefix of fullnames of this kind, e.g. t4 for messages.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::Comment => "t1",
+            Self::Account => "t2",
+            Self::Link => "t3",
+            Self::Message => "t4",
+            Self::Subreddit => "t5",
+            Self::Award => "t6",
+        }
+    }
+
+    /// The kind of the thing with the given fullname, e.g. [`Kind::Message`] for t4_abc123.
+    pub fn of_fullname(fullname: &str) -> Result<Self> {
+        std::convert::TryFrom::try_from(fullname.split('_').next().unwrap_or(fullname))
+    }
+}
+
+impl std::convert::TryFrom<&str> for Kind {
+    type Error = crate::reddit::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "t1" => Ok(Self::Comment),
+            "t2" => Ok(Self::Account),
+            "t3" => Ok(Self::Link),
+            "t4" => Ok(Self::Message),
+            "t5" => Ok(Self::Subreddit),
+            "t6" => Ok(Self::Award),
+            _ => Err(crate::reddit::Error::KindParseError(value.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+/// The sorts a post can suggest for its comments, see [`Post::set_suggested_sort`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentSort {
+    /// Called 'best' on the site.
+    Confidence,
+    Top,
+    New,
+    Controversial,
+    Old,
+    Random,
+    /// Answers by the author first.
+    QA,
+    Live,
+}
+
+impl CommentSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confidence => "confidence",
+            Self::Top => "top",
+            Self::New => "new",
+            Self::Controversial => "controversial",
+            Self::Old => "old",
+            Self::Random => "random",
+            Self::QA => "qa",
+            Self::Live => "live",
+        }
+    }
+}
+
+// Moderator actions on posts and comments, which all take the fullname as `id`.
+fn moderate(
+    client: &AuthenticatedClient,
+    endpoint: &str,
+    fullname: &str,
+    form: &[(&str, &str)],
+) -> Result<()> {
+    client.require_login()?;
+    client.require_scope(Scope::ModPosts)?;
 
-// impl std::convert::TryFrom<&str> for Kind {
-//     type Error = crate::reddit::Error;
+    let mut form = form.to_vec();
+    form.extend(&[("id", fullname), ("api_type", "json")]);
 
-//     fn try_from(value: &str) -> Result<Self, Self::Error> {
-//         match value {
-//             "t1" => Ok(Self::Comment),
-//             _ => Err(crate::reddit::Error::KindParseError),
-//         }
-//     }
-// }
+    client.post_api(&format!("{}/api/{}", crate::reddit::URL, endpoint), &form)?;
+
+    Ok(())
+}
+
+// Reddit takes the removal reason as JSON in a form field.
+fn add_removal_reason(
+    client: &AuthenticatedClient,
+    fullname: &str,
+    reason_id: Option<&str>,
+    mod_note: Option<&str>,
+) -> Result<()> {
+    client.post(
+        &format!("{}/api/v1/modactions/removal_reasons", crate::reddit::URL),
+        &[(
+            "json",
+            serde_json::json!({
+                "item_ids": [fullname],
+                "reason_id": reason_id,
+                "mod_note": mod_note,
+            })
+            .to_string(),
+        )],
+    )?;
+
+    Ok(())
+}
+
+// The rules of a subreddit. Reddit also returns the site-wide rules, which are ignored.
+#[derive(Deserialize, Debug)]
+struct RawRules {
+    rules: Vec<Rule>,
+}
 
 // Discard all the JSON data
 #[derive(Deserialize, Debug)]
@@ -368,6 +1731,82 @@ pub(crate) mod raw {
             #[serde(rename = "over_18")]
             pub(crate) nsfw: bool,
             pub(crate) id: String,
+            #[serde(flatten)]
+            pub(crate) reports: super::reports::RawReports,
+        }
+    }
+
+    pub(crate) mod message {
+        use serde::Deserialize;
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub(crate) struct RawMessageData {
+            pub(crate) id: String,
+            pub(crate) author: Option<String>,
+            pub(crate) dest: String,
+            pub(crate) subject: String,
+            pub(crate) body: String,
+            pub(crate) subreddit: Option<String>,
+            pub(crate) new: bool,
+            pub(crate) was_comment: bool,
+            pub(crate) context: Option<String>,
+            pub(crate) link_title: Option<String>,
+            pub(crate) parent_id: Option<String>,
+            pub(crate) created_utc: f64,
+        }
+    }
+
+    pub(crate) mod reports {
+        use serde::Deserialize;
+
+        // Null for anyone but moderators.
+        #[derive(Debug, Clone, Deserialize)]
+        pub(crate) struct RawReports {
+            #[serde(default)]
+            pub(crate) num_reports: Option<u32>,
+            #[serde(default)]
+            pub(crate) user_reports: Option<Vec<Vec<serde_json::Value>>>,
+            #[serde(default)]
+            pub(crate) mod_reports: Option<Vec<Vec<serde_json::Value>>>,
+        }
+    }
+
+    pub(crate) mod item {
+        use super::{comment::RawCommentData, post::RawPostData};
+        use serde::Deserialize;
+
+        // Listings that mix posts and comments.
+        #[derive(Debug, Deserialize)]
+        #[serde(tag = "kind", content = "data")]
+        pub(crate) enum RawItem {
+            #[serde(rename = "t3")]
+            Post(RawPostData),
+            #[serde(rename = "t1")]
+            Comment(RawCommentData),
+        }
+    }
+
+    pub(crate) mod multi {
+        use crate::things::Visibility;
+        use serde::Deserialize;
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub(crate) struct RawMultiData {
+            pub(crate) name: String,
+            pub(crate) display_name: String,
+            pub(crate) owner: String,
+            pub(crate) path: String,
+            pub(crate) description_md: String,
+            pub(crate) visibility: Visibility,
+            pub(crate) subreddits: Vec<RawMultiSubreddit>,
+            pub(crate) over_18: bool,
+            pub(crate) can_edit: bool,
+            pub(crate) created_utc: f64,
+        }
+
+        #[derive(Debug, Clone, Deserialize)]
+        pub(crate) struct RawMultiSubreddit {
+            pub(crate) name: String,
         }
     }
 
@@ -379,6 +1818,15 @@ pub(crate) mod raw {
             pub(crate) author: String,
             pub(crate) body: String,
             pub(crate) id: String,
+            pub(crate) score: i32,
+            pub(crate) subreddit: String,
+            pub(crate) link_id: String,
+            pub(crate) link_title: Option<String>,
+            pub(crate) parent_id: String,
+            pub(crate) permalink: String,
+            pub(crate) created_utc: f64,
+            #[serde(flatten)]
+            pub(crate) reports: super::reports::RawReports,
         }
     }
 }